@@ -0,0 +1,184 @@
+use crate::{Monitor, MonitorSetup};
+
+/// A side of a `Monitor`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    /// Yields the side facing this one, e.g. `Left` for `Right`.
+    pub fn opposite(&self) -> Side {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+/// A segment of a Monitor's edge which is shared with a neighboring Monitor.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SharedEdge {
+    /// The side of the Monitor on which the segment lies.
+    side: Side,
+    /// Start of the segment; this is an x coordinate for `Top` and `Bottom` edges, and a y
+    /// coordinate for `Left` and `Right` edges.
+    start: u32,
+    /// End of the segment (exclusive), along the same axis as `start`.
+    end: u32,
+}
+
+impl SharedEdge {
+    /// Yields the side of the Monitor on which the segment lies.
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    /// Yields the start of the segment; this is an x coordinate for `Top` and `Bottom` edges, and
+    /// a y coordinate for `Left` and `Right` edges.
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Yields the (exclusive) end of the segment, along the same axis as `start`.
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// Yields the length of the segment in pixels.
+    pub fn length(&self) -> u32 {
+        self.end - self.start
+    }
+}
+
+/// Describes a Monitor touching a neighboring Monitor along a shared edge.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Adjacency<'a> {
+    monitor: &'a Monitor,
+    neighbor: &'a Monitor,
+    edge: SharedEdge,
+}
+
+impl<'a> Adjacency<'a> {
+    /// Yields the Monitor whose edge is shared.
+    pub fn monitor(&self) -> &'a Monitor {
+        self.monitor
+    }
+
+    /// Yields the Monitor on the other side of the shared edge.
+    pub fn neighbor(&self) -> &'a Monitor {
+        self.neighbor
+    }
+
+    /// Yields the shared edge, from the perspective of `monitor`.
+    pub fn edge(&self) -> &SharedEdge {
+        &self.edge
+    }
+}
+
+/// A graph of the Monitors in a `MonitorSetup`, where Monitors are connected if they share an
+/// edge. Monitors which only touch at a corner are not considered adjacent.
+#[derive(Clone, Debug)]
+pub struct AdjacencyGraph<'a> {
+    /// Every adjacency in the graph; each pair of neighbors appears twice, once from the
+    /// perspective of each Monitor.
+    adjacencies: Vec<Adjacency<'a>>,
+}
+
+impl<'a> AdjacencyGraph<'a> {
+    /// Yields every adjacency in the graph. Each pair of neighboring Monitors appears twice, once
+    /// from the perspective of each Monitor.
+    pub fn adjacencies(&self) -> &[Adjacency<'a>] {
+        &self.adjacencies
+    }
+
+    /// Yields the adjacencies of the given Monitor.
+    pub fn neighbors<'b>(
+        &'b self,
+        monitor: &'b Monitor,
+    ) -> impl DoubleEndedIterator<Item = &'b Adjacency<'a>> {
+        self.adjacencies
+            .iter()
+            .filter(move |adjacency| adjacency.monitor == monitor)
+    }
+
+    /// Yields the neighbor sharing the longest edge with the given side of the Monitor, if any.
+    pub fn neighbor_on(&self, monitor: &Monitor, side: Side) -> Option<&'a Monitor> {
+        self.neighbors(monitor)
+            .filter(|adjacency| adjacency.edge.side == side)
+            // `max_by_key` yields the last maximum, so reverse to prefer the earliest neighbor
+            .rev()
+            .max_by_key(|adjacency| adjacency.edge.length())
+            .map(|adjacency| adjacency.neighbor)
+    }
+
+    /// Returns `true` if the two Monitors share an edge.
+    pub fn are_adjacent(&self, first: &Monitor, second: &Monitor) -> bool {
+        self.neighbors(first)
+            .any(|adjacency| adjacency.neighbor == second)
+    }
+}
+
+/// Yields the overlap between the segments `[a_start, a_end)` and `[b_start, b_end)`, if it is
+/// non-empty.
+fn overlap((a_start, a_end): (u32, u32), (b_start, b_end): (u32, u32)) -> Option<(u32, u32)> {
+    let start = a_start.max(b_start);
+    let end = a_end.min(b_end);
+
+    (end > start).then_some((start, end))
+}
+
+/// Yields the edge of `monitor` which is shared with `neighbor`, if any.
+fn shared_edge(monitor: &Monitor, neighbor: &Monitor) -> Option<SharedEdge> {
+    let (a, b) = (&monitor.rect, &neighbor.rect);
+
+    // saturate, so that Monitors reaching past the end of the coordinate space are clipped
+    let a_horizontal = (a.offset.x(), a.offset.x().saturating_add(a.width));
+    let a_vertical = (a.offset.y(), a.offset.y().saturating_add(a.height));
+    let b_horizontal = (b.offset.x(), b.offset.x().saturating_add(b.width));
+    let b_vertical = (b.offset.y(), b.offset.y().saturating_add(b.height));
+
+    let (side, segment) = if a_horizontal.1 == b_horizontal.0 {
+        (Side::Right, overlap(a_vertical, b_vertical))
+    } else if b_horizontal.1 == a_horizontal.0 {
+        (Side::Left, overlap(a_vertical, b_vertical))
+    } else if a_vertical.1 == b_vertical.0 {
+        (Side::Bottom, overlap(a_horizontal, b_horizontal))
+    } else if b_vertical.1 == a_vertical.0 {
+        (Side::Top, overlap(a_horizontal, b_horizontal))
+    } else {
+        return None;
+    };
+
+    segment.map(|(start, end)| SharedEdge { side, start, end })
+}
+
+impl MonitorSetup {
+    /// Yields a graph describing which Monitors share an edge, along with the shared segments.
+    pub fn adjacency(&self) -> AdjacencyGraph<'_> {
+        let mut adjacencies = Vec::new();
+
+        for (index, monitor) in self.monitors.iter().enumerate() {
+            for (neighbor_index, neighbor) in self.monitors.iter().enumerate() {
+                if index == neighbor_index {
+                    continue;
+                }
+
+                if let Some(edge) = shared_edge(monitor, neighbor) {
+                    adjacencies.push(Adjacency {
+                        monitor,
+                        neighbor,
+                        edge,
+                    });
+                }
+            }
+        }
+
+        AdjacencyGraph { adjacencies }
+    }
+}
@@ -1,27 +1,193 @@
-use monitor_utils::{x11::XRandrMonitorLoader, Monitor, MonitorSetup, Point, Rect};
+use monitor_utils::{
+    drm::DrmMonitorLoader,
+    file::FileMonitorLoader,
+    wayland::{Compositor, HyprlandEvents, HyprlandMonitorLoader, SwayEvents, SwayMonitorLoader},
+    x11::{X11Windows, XRandrEvents, XRandrMonitorLoader},
+    CoordinateSpace, MapMode, Monitor, MonitorSetup, OutputInfo, OutputInventory, Point, Profiles,
+    Rect, Reflection, Rotation, Scale, SetupDiff, TraversalOrder,
+};
 
 use bpaf::{construct, long, positional, short, OptionParser, Parser};
 
+use miniserde::{json, Serialize};
+
+use std::path::PathBuf;
+
 use anyhow::{anyhow, Result};
 
 #[derive(Debug, Clone)]
 enum Action {
     // need to have the () to satisfy bpaf
     MonitorAtPoint((), Point),
+    MonitorForRect((), Rect),
+    MonitorByXinerama(u32),
+    MonitorForActiveWindow,
+    MonitorForWindow(u32),
+
+    // `None` refers to the active window
+    MoveWindow(Option<u32>),
 
     NextMonitorClockwise,
     NextMonitorCounterClockwise,
     MonitorCenter,
     MonitorGeometry,
+    MapRectTo(MapMode),
+    MonitorXineramaIndex,
+}
+
+#[derive(Debug, Clone)]
+enum Command {
+    Apply {
+        dry_run: bool,
+        file: PathBuf,
+    },
+    ProfileSave(String),
+    ProfileLoad {
+        dry_run: bool,
+        name: String,
+    },
+    ProfileList,
+    ProfileDetect {
+        apply: bool,
+    },
+    Outputs {
+        connected: bool,
+    },
+    InputMatrix {
+        dry_run: bool,
+        devices: Vec<String>,
+        monitor: String,
+    },
+    Watch,
+    Diff {
+        before: PathBuf,
+        after: PathBuf,
+    },
 }
 
 #[derive(Debug)]
 struct Options {
     refresh: bool,
+    drm: bool,
+    from_file: Option<PathBuf>,
+    from_stdin: bool,
+    layout: Option<Vec<Monitor>>,
     shell_output: bool,
+    check: bool,
+    diff_cache: bool,
+    order: Option<TraversalOrder>,
+    physical: bool,
 
     // actions are pipelined from left to right
     actions: Vec<Action>,
+
+    // commands replace the pipeline entirely
+    command: Option<Command>,
+}
+
+fn dry_run() -> impl Parser<bool> {
+    long("dry-run")
+        .help("If specified, prints the xrandr command instead of running it")
+        .req_flag(true)
+        .fallback(false)
+}
+
+fn apply_command() -> impl Parser<Command> {
+    let dry_run = dry_run();
+    let file = positional::<PathBuf>("FILE").help("JSON file containing the monitor setup");
+
+    construct!(Command::Apply { dry_run, file })
+        .to_options()
+        .descr("Applies a monitor setup stored as JSON through xrandr")
+        .command("apply")
+}
+
+fn profile_command() -> impl Parser<Command> {
+    let name = positional::<String>("NAME").help("Name of the profile");
+    let save = construct!(Command::ProfileSave(name))
+        .to_options()
+        .descr("Saves the current monitor setup as a profile")
+        .command("save");
+
+    let dry_run = dry_run();
+    let name = positional::<String>("NAME").help("Name of the profile");
+    let load = construct!(Command::ProfileLoad { dry_run, name })
+        .to_options()
+        .descr(
+            "Applies a profile through xrandr, turning off any connected outputs it does not use",
+        )
+        .command("load");
+
+    let list = bpaf::pure(Command::ProfileList)
+        .to_options()
+        .descr("Lists the saved profiles")
+        .command("list");
+
+    let apply = long("apply")
+        .help("If specified, also applies the detected profile")
+        .req_flag(true)
+        .fallback(false);
+    let detect = construct!(Command::ProfileDetect { apply })
+        .to_options()
+        .descr("Prints the profile made for the currently connected outputs, exiting with a non-zero code if there is none")
+        .command("detect");
+
+    construct!([save, load, list, detect])
+        .to_options()
+        .descr("Saves and restores named monitor setups")
+        .command("profile")
+}
+
+fn outputs_command() -> impl Parser<Command> {
+    let connected = long("connected")
+        .help("If specified, only lists outputs which have a display connected")
+        .req_flag(true)
+        .fallback(false);
+
+    construct!(Command::Outputs { connected })
+        .to_options()
+        .descr("Lists every output, including disconnected and turned-off outputs, with its capabilities")
+        .command("outputs")
+}
+
+fn input_matrix_command() -> impl Parser<Command> {
+    let dry_run = long("dry-run")
+        .help("If specified, prints the xinput commands instead of running them")
+        .req_flag(true)
+        .fallback(false);
+    let devices = long("device")
+        .help("Sets the Coordinate Transformation Matrix of the given xinput device, by name or ID, instead of printing the matrix; may be repeated, e.g. for the stylus and eraser of a tablet")
+        .argument::<String>("DEVICE")
+        .many();
+    let monitor = positional::<String>("MONITOR").help(
+        "The monitor to restrict the device to: an adapter name, 'primary', or a Xinerama index",
+    );
+
+    construct!(Command::InputMatrix {
+        dry_run,
+        devices,
+        monitor
+    })
+    .to_options()
+    .descr("Prints or applies the input matrix mapping a touchscreen or tablet onto a monitor")
+    .command("input-matrix")
+}
+
+fn diff_command() -> impl Parser<Command> {
+    let before = positional::<PathBuf>("BEFORE").help("JSON file containing the earlier setup");
+    let after = positional::<PathBuf>("AFTER").help("JSON file containing the later setup");
+
+    construct!(Command::Diff { before, after })
+        .to_options()
+        .descr("Prints the monitors added, removed and changed between two monitor setups stored as JSON, exiting with a non-zero code if there are any")
+        .command("diff")
+}
+
+fn watch_command() -> impl Parser<Command> {
+    bpaf::pure(Command::Watch)
+        .to_options()
+        .descr("Waits for changes to the monitor layout, refreshing the cache and printing the differences after each change as a line of JSON, like the diff command; listens to RandR on X11, and to the output events of sway or Hyprland on Wayland")
+        .command("watch")
 }
 
 fn cli() -> OptionParser<Options> {
@@ -31,12 +197,54 @@ fn cli() -> OptionParser<Options> {
         .req_flag(true)
         .fallback(false);
 
+    let drm = long("drm")
+        .help("If specified, reads the connected and enabled monitors from /sys/class/drm instead of the display server, which works from a TTY; the cache is neither read nor written")
+        .req_flag(true)
+        .fallback(false);
+
+    let from_file = long("from-file")
+        .help("If specified, reads the monitors from a JSON or TOML layout file instead of the display server; the cache is neither read nor written")
+        .argument::<PathBuf>("PATH")
+        .optional();
+
+    let from_stdin = long("from-stdin")
+        .help("If specified, reads the monitors from a JSON or TOML layout on stdin, like --from-file")
+        .req_flag(true)
+        .fallback(false);
+
+    let layout = long("layout")
+        .help("If specified, uses the given monitors instead of the display server, e.g. 'eDP-1:1920x1080+0+0,HDMI-1:2560x1440+1920+0'; the cache is neither read nor written")
+        .argument::<String>("SPEC")
+        .parse(|spec| parse_layout(&spec))
+        .optional();
+
     let shell_output = short('s')
         .long("shell")
         .help("If specified, spit out output in POSIX shell variable format, such that it may be eval'd")
         .req_flag(true)
         .fallback(false);
 
+    let check = long("check")
+        .help("If specified, validates the monitor layout instead of running actions, printing any issues and exiting with a non-zero code if the layout is broken")
+        .req_flag(true)
+        .fallback(false);
+
+    let diff_cache = long("diff-cache")
+        .help("If specified, compares the current monitor layout with the cached one instead of running actions, printing the differences like the diff command and refreshing the cache")
+        .req_flag(true)
+        .fallback(false);
+
+    let order = long("order")
+        .help("Sets the order in which --clockwise and --counter-clockwise traverse monitors: 'origin' (clockwise around the top-left corner of the screen; the default), 'centroid' (clockwise around the center of the layout), 'rows', 'columns', 'name', 'xinerama', or a comma-separated list of adapter names")
+        .argument::<String>("ORDER")
+        .parse(|order| parse_traversal_order(&order))
+        .optional();
+
+    let physical = long("physical")
+        .help("If specified, --at-point takes and --center and --geometry yield physical pixels instead of logical coordinates, e.g. to match screenshots on HiDPI setups")
+        .req_flag(true)
+        .fallback(false);
+
     let clockwise = long("clockwise")
         .help("Given an argument monitor, yields the next monitor in a clockwise rotation.")
         .req_flag(Action::NextMonitorClockwise);
@@ -53,7 +261,17 @@ fn cli() -> OptionParser<Options> {
         .help("Given an argument monitor, yields the geometry of the monitor.")
         .req_flag(Action::MonitorGeometry);
 
-    let monitor_actions = construct!([clockwise, counter_clockwise, center, geometry]).group_help("These commands each take in a Monitor through the pipeline, and yield either a Point or another Monitor.");
+    let map_rect_to = long("map-rect-to")
+        .help("Given an argument monitor, yields the rect from the most recent --for-rect, --active-window or --window command, mapped onto the monitor. MODE is one of 'pixels', 'relative' or 'maximized'.")
+        .argument::<String>("MODE")
+        .parse(|mode| parse_map_mode(&mode))
+        .map(Action::MapRectTo);
+
+    let xinerama_index = long("xinerama-index")
+        .help("Given an argument monitor, yields its Xinerama screen index, as used by e.g. `dmenu -m` and `rofi -m`.")
+        .req_flag(Action::MonitorXineramaIndex);
+
+    let monitor_actions = construct!([clockwise, counter_clockwise, center, geometry, map_rect_to, xinerama_index]).group_help("These commands each take in a Monitor through the pipeline, and yield either a Point or another Monitor.");
 
     fn monitor_at_point() -> impl Parser<Action> {
         let monitor_at_point = long("at-point").req_flag(()).group_help(
@@ -67,12 +285,78 @@ fn cli() -> OptionParser<Options> {
         construct!(Action::MonitorAtPoint(monitor_at_point, point)).adjacent()
     }
 
-    let actions = construct!([monitor_at_point(), monitor_actions]).many().group_help("The following options are commands, which pipeline data from the left of the command-line to the right.");
+    fn monitor_for_rect() -> impl Parser<Action> {
+        let monitor_for_rect = long("for-rect").req_flag(()).group_help(
+            "Takes 4 arguments: X, Y, W and H, and yields the monitor with the largest overlap with the rectangle of size WxH at (X,Y)",
+        );
+        let x = positional::<u32>("X");
+        let y = positional::<u32>("Y");
+        let width = positional::<u32>("W");
+        let height = positional::<u32>("H");
+
+        // positionals are consumed in the order they are listed, so build the Rect afterwards
+        let rect = construct!(x, y, width, height)
+            .map(|(x, y, width, height)| Rect::new(width, height, Point::new(x, y)));
+
+        construct!(Action::MonitorForRect(monitor_for_rect, rect)).adjacent()
+    }
+
+    let active_window = long("active-window")
+        .help("Yields the monitor which the active window mostly lies on.")
+        .req_flag(Action::MonitorForActiveWindow);
+
+    let window = long("window")
+        .help("Takes a window ID in decimal or hexadecimal, and yields the monitor which the window mostly lies on.")
+        .argument::<String>("ID")
+        .parse(|id| parse_window_id(&id))
+        .map(Action::MonitorForWindow);
+
+    let window_actions = construct!([active_window, window]).group_help(
+        "These commands query the X11 window manager, and yield the monitor containing a window.",
+    );
+
+    let move_window = long("move-window")
+        .help("Given an argument rect, moves and resizes the window with the given ID (or the active window, if given 'active') to occupy it, and yields the rect.")
+        .argument::<String>("ID|active")
+        .parse(|id| match id.as_str() {
+            "active" => Ok(None),
+            id => parse_window_id(id).map(Some),
+        })
+        .map(Action::MoveWindow);
+
+    let rect_actions = construct!([move_window])
+        .group_help("These commands each take in a Rect through the pipeline.");
+
+    let by_xinerama = long("by-xinerama")
+        .help("Takes a Xinerama screen index N, and yields the corresponding monitor.")
+        .argument::<u32>("N")
+        .map(Action::MonitorByXinerama);
+
+    let actions = construct!([monitor_at_point(), monitor_for_rect(), by_xinerama, window_actions, monitor_actions, rect_actions]).many().group_help("The following options are commands, which pipeline data from the left of the command-line to the right.");
+
+    let command = construct!([
+        apply_command(),
+        profile_command(),
+        outputs_command(),
+        input_matrix_command(),
+        watch_command(),
+        diff_command()
+    ])
+    .optional();
 
     let parser = construct!(Options {
         shell_output,
         refresh,
-        actions
+        drm,
+        from_file,
+        from_stdin,
+        layout,
+        check,
+        diff_cache,
+        order,
+        physical,
+        actions,
+        command
     });
 
     parser
@@ -81,55 +365,597 @@ fn cli() -> OptionParser<Options> {
         .descr("CLI for monitor-utils")
 }
 
+/// Parses a window ID, which may be given either in decimal (as printed by `xdotool`) or in
+/// hexadecimal with a `0x` prefix (as printed by `xprop` and `xwininfo`).
+fn parse_window_id(id: &str) -> Result<u32> {
+    let parsed = match id.strip_prefix("0x").or_else(|| id.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => id.parse(),
+    };
+
+    parsed.map_err(|_| anyhow!("Invalid window ID: {}", id))
+}
+
+/// Parses an inline layout of the form `NAME:WxH+X+Y,NAME:WxH+X+Y,...`.
+fn parse_layout(spec: &str) -> Result<Vec<Monitor>> {
+    let mut monitors: Vec<Monitor> = Vec::new();
+
+    for (index, entry) in spec.split(',').enumerate() {
+        let (name, geometry) = entry
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Invalid monitor {:?}; expected NAME:WxH+X+Y", entry))?;
+
+        if name.is_empty() {
+            return Err(anyhow!("Missing monitor name in {:?}", entry));
+        }
+
+        if monitors.iter().any(|monitor| monitor.name() == name) {
+            return Err(anyhow!("Duplicate monitor name: {}", name));
+        }
+
+        monitors.push(Monitor::new(
+            name.to_owned(),
+            index as u32,
+            geometry.parse::<Rect>()?,
+        ));
+    }
+
+    Ok(monitors)
+}
+
+fn parse_map_mode(mode: &str) -> Result<MapMode> {
+    match mode {
+        "pixels" => Ok(MapMode::PreservePixels),
+        "relative" => Ok(MapMode::PreserveRelative),
+        "maximized" => Ok(MapMode::PreserveMaximized),
+        _ => Err(anyhow!(
+            "Invalid mode: {}; expected 'pixels', 'relative' or 'maximized'",
+            mode
+        )),
+    }
+}
+
+fn parse_traversal_order(order: &str) -> Result<TraversalOrder> {
+    match order {
+        "origin" => Ok(TraversalOrder::ClockwiseFromOrigin),
+        "centroid" => Ok(TraversalOrder::ClockwiseAroundCentroid),
+        "rows" => Ok(TraversalOrder::RowMajor),
+        "columns" => Ok(TraversalOrder::ColumnMajor),
+        "name" => Ok(TraversalOrder::ByName),
+        "xinerama" => Ok(TraversalOrder::ByXineramaIndex),
+        names => Ok(TraversalOrder::Explicit(
+            names.split(',').map(str::to_owned).collect(),
+        )),
+    }
+}
+
+/// Yields the given window ID, or the ID of the active window if none was given.
+fn resolve_window(windows: &X11Windows, window: Option<u32>) -> Result<u32> {
+    match window {
+        Some(window) => Ok(window),
+        None => Ok(windows.active_window()?),
+    }
+}
+
+/// Runs `xrandr` with the given arguments, or prints the command if `dry_run` is set.
+/// Since the layout changes, the cache is refreshed afterwards.
+fn run_xrandr(args: &[String], dry_run: bool) -> Result<()> {
+    if dry_run {
+        println!("xrandr {}", args.join(" "));
+        return Ok(());
+    }
+
+    let status = std::process::Command::new("xrandr").args(args).status()?;
+    if !status.success() {
+        return Err(anyhow!("xrandr returned with {}", status));
+    }
+
+    MonitorSetup::with_loader(XRandrMonitorLoader::new()?)?.to_global_cache()?;
+
+    Ok(())
+}
+
+/// Joins the arguments of a command such that they can be pasted into a shell, quoting those
+/// which contain spaces or other special characters.
+fn shell_quote(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            let safe = |c: char| c.is_ascii_alphanumeric() || "-_.,:/=+".contains(c);
+
+            if !arg.is_empty() && arg.chars().all(safe) {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Applies the given profile, turning off every connected output which it does not use.
+fn load_profile(profiles: &Profiles, name: &str, dry_run: bool) -> Result<()> {
+    let connected = XRandrMonitorLoader::new()?.connected_outputs()?;
+    let setup = profiles.load_for_outputs(name, &connected)?;
+    let mut args = setup.to_xrandr_args();
+
+    for output in connected {
+        if setup.monitor_by_name(output.name()).is_none() {
+            args.extend([
+                "--output".to_owned(),
+                output.name().to_owned(),
+                "--off".to_owned(),
+            ]);
+        }
+    }
+
+    run_xrandr(&args, dry_run)
+}
+
+/// Formats a rectangle as `WxH+X+Y`, like `xrandr` does.
+fn format_geometry(rect: &Rect) -> String {
+    format!(
+        "{}x{}+{}+{}",
+        rect.width(),
+        rect.height(),
+        rect.offset().x(),
+        rect.offset().y()
+    )
+}
+
+/// A monitor which was added or removed, in the JSON printed by `diff`, `--diff-cache` and
+/// `watch`.
+#[derive(Serialize)]
+struct DiffMonitor {
+    name: String,
+    geometry: String,
+}
+
+/// A monitor which is in both layouts, in the JSON printed by `diff`, `--diff-cache` and `watch`.
+#[derive(Serialize)]
+struct DiffChange {
+    name: String,
+    /// Any of "moved", "resized", "rotated" and "primary".
+    changes: Vec<String>,
+    from: String,
+    to: String,
+    rotation: String,
+    primary: bool,
+}
+
+#[derive(Serialize)]
+struct DiffJson {
+    added: Vec<DiffMonitor>,
+    removed: Vec<DiffMonitor>,
+    changed: Vec<DiffChange>,
+}
+
+/// Formats the differences between two layouts as a single line of JSON.
+fn diff_json(diff: &SetupDiff) -> String {
+    let monitor = |monitor: &&Monitor| DiffMonitor {
+        name: monitor.name().to_owned(),
+        geometry: format_geometry(monitor.rect()),
+    };
+
+    let changed = diff
+        .changed()
+        .iter()
+        .map(|change| {
+            let (before, after) = (change.before(), change.after());
+            let changes = [
+                (change.is_moved(), "moved"),
+                (change.is_resized(), "resized"),
+                (change.is_rotated(), "rotated"),
+                (change.is_primary_changed(), "primary"),
+            ];
+
+            DiffChange {
+                name: after.name().to_owned(),
+                changes: changes
+                    .iter()
+                    .filter(|(set, _)| *set)
+                    .map(|(_, name)| (*name).to_owned())
+                    .collect(),
+                from: format_geometry(before.rect()),
+                to: format_geometry(after.rect()),
+                rotation: after.rotation().name().to_owned(),
+                primary: after.is_primary(),
+            }
+        })
+        .collect();
+
+    json::to_string(&DiffJson {
+        added: diff.added().iter().map(monitor).collect(),
+        removed: diff.removed().iter().map(monitor).collect(),
+        changed,
+    })
+}
+
+/// Prints the differences between two layouts as JSON, exiting with a non-zero code if there are
+/// any, like `diff` does.
+fn print_diff(before: &MonitorSetup, after: &MonitorSetup) {
+    let diff = before.diff(after);
+    println!("{}", diff_json(&diff));
+
+    if !diff.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+/// The events by which the display server reports changes to the monitor layout.
+enum LayoutEvents {
+    XRandr(Box<XRandrEvents>),
+    Sway(SwayEvents),
+    Hyprland(HyprlandEvents),
+}
+
+impl LayoutEvents {
+    /// Subscribes to the events of the running compositor, or of RandR if there is none.
+    fn new() -> Result<LayoutEvents> {
+        Ok(match Compositor::detect() {
+            Some(Compositor::Sway) => LayoutEvents::Sway(SwayEvents::new()?),
+            Some(Compositor::Hyprland) => LayoutEvents::Hyprland(HyprlandEvents::new()?),
+            None => LayoutEvents::XRandr(Box::new(XRandrEvents::new()?)),
+        })
+    }
+
+    fn wait_for_change(&mut self) -> Result<()> {
+        match self {
+            LayoutEvents::XRandr(events) => events.wait_for_change()?,
+            LayoutEvents::Sway(events) => events.wait_for_change()?,
+            LayoutEvents::Hyprland(events) => events.wait_for_change()?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Reloads the layout whenever the display server reports a change, printing the differences as
+/// JSON lines.
+fn watch() -> Result<()> {
+    // subscribe first, so that no change between loading the layout and waiting is missed
+    let mut events = LayoutEvents::new()?;
+    let mut setup = display_server_setup()?;
+    setup.to_global_cache()?;
+
+    loop {
+        events.wait_for_change()?;
+
+        let current = display_server_setup()?;
+        current.to_global_cache()?;
+
+        // a single change yields several events, most of which leave the layout as it is
+        let diff = setup.diff(&current);
+        if !diff.is_empty() {
+            println!("{}", diff_json(&diff));
+        }
+
+        setup = current;
+    }
+}
+
+/// Prints an output along with its capabilities, skipping anything the loader does not report.
+fn print_output(output: &OutputInfo) {
+    let mut state = vec![output.connection().to_string()];
+
+    match output.rect() {
+        Some(rect) => state.push(format!("enabled at {}", format_geometry(rect))),
+        None if output.is_enabled() => state.push("enabled".to_owned()),
+        None if output.is_connected() => state.push("disabled".to_owned()),
+        None => {}
+    }
+
+    if output.rotation() != Rotation::Normal {
+        state.push(format!("rotated {}", output.rotation().name()));
+    }
+    if output.reflection() != Reflection::Normal {
+        state.push(format!("reflected {}", output.reflection().name()));
+    }
+    if output.scale() != Scale::ONE {
+        state.push(format!("scaled {}", output.scale()));
+    }
+    if output.is_primary() {
+        state.push("primary".to_owned());
+    }
+
+    println!("{}: {}", output.name(), state.join(", "));
+
+    let mut display = Vec::new();
+    if let Some(identity) = output.identity() {
+        display.push(identity.to_string());
+    }
+    if let Some((width, height)) = output.physical_size() {
+        display.push(format!("{}mm x {}mm", width, height));
+    }
+    if !display.is_empty() {
+        println!("    display: {}", display.join(", "));
+    }
+
+    if !output.modes().is_empty() {
+        let modes = output
+            .modes()
+            .iter()
+            .map(|mode| {
+                let flags = [
+                    (mode.is_preferred(), "preferred"),
+                    (mode.is_current(), "current"),
+                ]
+                .iter()
+                .filter(|(set, _)| *set)
+                .map(|(_, flag)| *flag)
+                .collect::<Vec<_>>();
+
+                if flags.is_empty() {
+                    mode.to_string()
+                } else {
+                    format!("{} ({})", mode, flags.join(", "))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        println!("    modes: {}", modes.join(", "));
+    }
+
+    if !output.rotations().is_empty() {
+        let rotations = output
+            .rotations()
+            .iter()
+            .map(|rotation| rotation.name())
+            .collect::<Vec<_>>();
+
+        println!("    rotations: {}", rotations.join(", "));
+    }
+
+    if !output.possible_crtcs().is_empty() {
+        let crtcs = output
+            .possible_crtcs()
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>();
+
+        println!("    crtcs: {}", crtcs.join(", "));
+    }
+}
+
+/// Resolves a monitor selector, which is either an adapter name, `primary`, or a Xinerama index.
+fn select_monitor<'a>(setup: &'a MonitorSetup, selector: &str) -> Result<&'a Monitor> {
+    if selector == "primary" {
+        return setup
+            .monitors()
+            .iter()
+            .find(|monitor| monitor.is_primary())
+            .ok_or_else(|| anyhow!("No primary monitor"));
+    }
+
+    if let Ok(index) = selector.parse::<u32>() {
+        return Ok(setup.monitor_by_xinerama_index(index)?);
+    }
+
+    setup
+        .monitor_by_name(selector)
+        .ok_or_else(|| anyhow!("Unknown monitor: {}", selector))
+}
+
+fn run_command(command: Command, options: &Options) -> Result<()> {
+    let profiles = Profiles::new();
+
+    match command {
+        Command::Apply { dry_run, file } => {
+            let setup = MonitorSetup::from_json(&std::fs::read_to_string(file)?)?;
+            run_xrandr(&setup.to_xrandr_args(), dry_run)?;
+        }
+        Command::ProfileSave(name) => {
+            // the cache may be stale, so always query the current setup
+            let setup = display_server_setup()?;
+            profiles.save(&name, &setup)?;
+        }
+        Command::ProfileLoad { dry_run, name } => load_profile(&profiles, &name, dry_run)?,
+        Command::ProfileList => {
+            for name in profiles.list()? {
+                println!("{}", name);
+            }
+        }
+        Command::ProfileDetect { apply } => {
+            let connected = XRandrMonitorLoader::new()?.connected_outputs()?;
+
+            match profiles.detect(&connected)? {
+                Some(name) => {
+                    println!("{}", name);
+
+                    if apply {
+                        load_profile(&profiles, &name, false)?;
+                    }
+                }
+                None => std::process::exit(1),
+            }
+        }
+        Command::Outputs { connected } => {
+            let inventory = if options.drm {
+                OutputInventory::with_loader(DrmMonitorLoader::new()?)?
+            } else {
+                OutputInventory::with_loader(XRandrMonitorLoader::new()?)?
+            };
+
+            for output in inventory.outputs() {
+                if !connected || output.is_connected() {
+                    print_output(output);
+                }
+            }
+        }
+        Command::InputMatrix {
+            dry_run,
+            devices,
+            monitor,
+        } => {
+            // the layout has usually just changed, so never read the cache
+            let setup = load_setup(options, false)?;
+            let matrix = setup.input_matrix(select_monitor(&setup, &monitor)?);
+
+            if devices.is_empty() {
+                println!("{}", matrix);
+            }
+
+            for device in devices {
+                if dry_run {
+                    let args = matrix.to_xinput_args(&device);
+                    println!("xinput {}", shell_quote(&args));
+                } else {
+                    matrix.apply_with_xinput(&device)?;
+                }
+            }
+        }
+        Command::Watch => watch()?,
+        Command::Diff { before, after } => {
+            let before = MonitorSetup::from_json(&std::fs::read_to_string(before)?)?;
+            let after = MonitorSetup::from_json(&std::fs::read_to_string(after)?)?;
+            print_diff(&before, &after);
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads the monitor setup from the source selected on the command line, or from the display
+/// server. The cache is only read if `use_cache` is set, and is refreshed whenever the display
+/// server is queried.
+fn load_setup(options: &Options, use_cache: bool) -> Result<MonitorSetup> {
+    if let Some(monitors) = &options.layout {
+        return Ok(MonitorSetup::new(monitors.clone()));
+    } else if let Some(path) = &options.from_file {
+        let loader = FileMonitorLoader::from_path(path);
+        return Ok(MonitorSetup::with_loader(loader)?);
+    } else if options.from_stdin {
+        let loader = FileMonitorLoader::from_stdin()?;
+        return Ok(MonitorSetup::with_loader(loader)?);
+    } else if options.drm {
+        return Ok(MonitorSetup::with_loader(DrmMonitorLoader::new()?)?);
+    } else if use_cache {
+        if let Ok(setup) = MonitorSetup::from_global_cache() {
+            return Ok(setup);
+        }
+    }
+
+    let setup = display_server_setup()?;
+    setup.to_global_cache()?;
+
+    Ok(setup)
+}
+
+/// Queries the monitor setup of the running compositor, or of the X11 display if there is none.
+fn display_server_setup() -> Result<MonitorSetup> {
+    let setup = match Compositor::detect() {
+        Some(Compositor::Sway) => MonitorSetup::with_loader(SwayMonitorLoader::new()?)?,
+        Some(Compositor::Hyprland) => MonitorSetup::with_loader(HyprlandMonitorLoader::new()?)?,
+        None => MonitorSetup::with_loader(XRandrMonitorLoader::new()?)?,
+    };
+
+    Ok(setup)
+}
+
 fn main() -> Result<()> {
     let cli = cli();
-    let options = cli.run();
+    let mut options = cli.run();
 
-    let mut monitor_setup = None;
+    if let Some(command) = options.command.take() {
+        return run_command(command, &options);
+    }
+
+    if options.diff_cache {
+        let cached = MonitorSetup::from_global_cache()?;
+        print_diff(&cached, &load_setup(&options, false)?);
+        return Ok(());
+    }
+
+    let mut monitor_setup = load_setup(&options, !options.refresh)?;
 
-    if !options.refresh {
-        match MonitorSetup::from_global_cache() {
-            Ok(setup) => {
-                monitor_setup.replace(setup);
+    if let Some(order) = &options.order {
+        // catch typos, which would otherwise silently leave the monitor out of the list
+        if let TraversalOrder::Explicit(names) = order {
+            if let Some(name) = names
+                .iter()
+                .find(|name| monitor_setup.monitor_by_name(name).is_none())
+            {
+                return Err(anyhow!("Unknown monitor in --order: {}", name));
             }
-            Err(_) => (),
-        };
+        }
+
+        monitor_setup.set_traversal_order(order);
     }
 
-    if monitor_setup.is_none() {
-        // use a different loader depending on enabled feature
-        #[cfg(feature = "x11")]
-        let loader = XRandrMonitorLoader::new()?;
+    if options.check {
+        let report = monitor_setup.validate();
+
+        for issue in report.issues() {
+            let severity = if issue.is_error() { "error" } else { "warning" };
+            println!("{}: {}", severity, issue);
+        }
 
-        // Example future code:
-        //
-        // #[cfg(feature = "wayland")]
-        // let loader = WaylandMonitorLoader::new()?;
+        if report.has_errors() {
+            std::process::exit(1);
+        }
 
-        let setup = MonitorSetup::with_loader(loader)?;
-        setup.to_global_cache()?;
-        monitor_setup.replace(setup);
+        return Ok(());
     }
 
-    let monitor_setup = monitor_setup.expect("Monitor setup must exist");
+    let space = if options.physical {
+        CoordinateSpace::Physical
+    } else {
+        CoordinateSpace::Logical
+    };
 
     // now, let's run our actions
+    #[allow(clippy::enum_variant_names)]
     enum Accumulator<'a> {
         AccumPoint(Point),
         AccumMonitor(&'a Monitor),
         AccumRect(Rect),
+        AccumXineramaIndex(u32),
     }
 
     use Accumulator::*;
     use Action::*;
 
+    // the most recent rect resolved to a monitor, along with that monitor, for `--map-rect-to`
+    let mut subject: Option<(Rect, &Monitor)> = None;
+
     let res = options
         .actions
         .into_iter()
         .try_fold(AccumPoint(Point::new(0, 0)), |acc, act| match act {
             MonitorAtPoint((), point) => Ok(AccumMonitor(
-                monitor_setup.monitor_containing_point(&point)?,
+                monitor_setup.monitor_containing_point_in(&point, space)?,
             )),
+            MonitorByXinerama(index) => Ok(AccumMonitor(
+                monitor_setup.monitor_by_xinerama_index(index)?,
+            )),
+            MonitorForRect((), rect) => {
+                let monitor = monitor_setup.monitor_for_rect(&rect)?.0;
+                subject = Some((rect, monitor));
+
+                Ok(AccumMonitor(monitor))
+            }
+            MonitorForActiveWindow | MonitorForWindow(_) => {
+                let window = match act {
+                    MonitorForWindow(window) => Some(window),
+                    _ => None,
+                };
+                let windows = X11Windows::new()?;
+                let rect = windows.frame_rect(resolve_window(&windows, window)?)?;
+                let monitor = monitor_setup.monitor_for_rect(&rect)?.0;
+                subject = Some((rect, monitor));
+
+                Ok(AccumMonitor(monitor))
+            }
+            MoveWindow(window) => {
+                let rect = match acc {
+                    AccumRect(rect) => rect,
+                    _ => return Err(anyhow!("Expected Rect in accumulator")),
+                };
+
+                let windows = X11Windows::new()?;
+                windows.move_resize(resolve_window(&windows, window)?, &rect)?;
+
+                Ok(AccumRect(rect))
+            }
             _ => {
                 let monitor = match acc {
                     AccumMonitor(monitor) => monitor,
@@ -145,8 +971,19 @@ fn main() -> Result<()> {
                             .next_monitor_counterclockwise(monitor)
                             .unwrap(),
                     )),
-                    MonitorCenter => Ok(AccumPoint(monitor.rect.center())),
-                    MonitorGeometry => Ok(AccumRect(monitor.rect.clone())),
+                    MonitorCenter => Ok(AccumPoint(monitor_setup.rect_in(monitor, space).center())),
+                    MonitorGeometry => Ok(AccumRect(monitor_setup.rect_in(monitor, space))),
+                    MonitorXineramaIndex => monitor
+                        .xinerama_index()
+                        .map(AccumXineramaIndex)
+                        .ok_or_else(|| anyhow!("{} has no Xinerama index", monitor.name())),
+                    MapRectTo(mode) => {
+                        let (rect, from) = subject.as_ref().ok_or_else(|| {
+                            anyhow!("--map-rect-to requires a preceding --for-rect, --active-window or --window")
+                        })?;
+
+                        Ok(AccumRect(monitor_setup.map_rect(rect, from, monitor, mode)))
+                    }
                     _ => unreachable!(),
                 }
             }
@@ -163,12 +1000,14 @@ fn main() -> Result<()> {
                 rect.width(),
                 rect.height(),
             ),
+            AccumXineramaIndex(index) => println!("XINERAMA_INDEX={}", index),
         }
     } else {
         match res {
             AccumPoint(point) => println!("{:?}", point),
             AccumMonitor(monitor) => println!("{}", monitor.name()),
             AccumRect(rect) => println!("{:?}", rect),
+            AccumXineramaIndex(index) => println!("{}", index),
         }
     }
 
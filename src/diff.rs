@@ -0,0 +1,151 @@
+use crate::pairing::{pair_outputs, OutputKey};
+use crate::{Monitor, MonitorSetup};
+
+/// A Monitor present in both setups of a `SetupDiff`, whose placement changed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MonitorChange<'a> {
+    before: &'a Monitor,
+    after: &'a Monitor,
+}
+
+impl<'a> MonitorChange<'a> {
+    /// Yields the Monitor as it was in the first setup.
+    pub fn before(&self) -> &'a Monitor {
+        self.before
+    }
+
+    /// Yields the Monitor as it is in the second setup, which may be on another adapter if the
+    /// display was plugged into another port.
+    pub fn after(&self) -> &'a Monitor {
+        self.after
+    }
+
+    /// Returns `true` if the top-left corner of the Monitor moved.
+    pub fn is_moved(&self) -> bool {
+        self.before.rect.offset != self.after.rect.offset
+    }
+
+    /// Returns `true` if the width or height of the Monitor changed, which includes sideways
+    /// rotations.
+    pub fn is_resized(&self) -> bool {
+        (self.before.rect.width, self.before.rect.height)
+            != (self.after.rect.width, self.after.rect.height)
+    }
+
+    /// Returns `true` if the rotation of the Monitor changed.
+    pub fn is_rotated(&self) -> bool {
+        self.before.rotation != self.after.rotation
+    }
+
+    /// Returns `true` if the Monitor became or stopped being the primary monitor.
+    pub fn is_primary_changed(&self) -> bool {
+        self.before.primary != self.after.primary
+    }
+
+    fn is_changed(&self) -> bool {
+        self.is_moved() || self.is_resized() || self.is_rotated() || self.is_primary_changed()
+    }
+}
+
+/// The differences between two `MonitorSetup`s, as yielded by `MonitorSetup::diff`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SetupDiff<'a> {
+    added: Vec<&'a Monitor>,
+    removed: Vec<&'a Monitor>,
+    changed: Vec<MonitorChange<'a>>,
+}
+
+impl<'a> SetupDiff<'a> {
+    /// Yields the Monitors of the second setup which are not in the first one.
+    pub fn added(&self) -> &[&'a Monitor] {
+        &self.added
+    }
+
+    /// Yields the Monitors of the first setup which are not in the second one.
+    pub fn removed(&self) -> &[&'a Monitor] {
+        &self.removed
+    }
+
+    /// Yields every Monitor which is in both setups and was moved, resized, rotated or
+    /// re-primaried, in the order of the second setup.
+    pub fn changed(&self) -> &[MonitorChange<'a>] {
+        &self.changed
+    }
+
+    /// Yields the Monitors whose top-left corner moved.
+    pub fn moved(&self) -> impl Iterator<Item = &MonitorChange<'a>> {
+        self.changed.iter().filter(|change| change.is_moved())
+    }
+
+    /// Yields the Monitors whose width or height changed.
+    pub fn resized(&self) -> impl Iterator<Item = &MonitorChange<'a>> {
+        self.changed.iter().filter(|change| change.is_resized())
+    }
+
+    /// Yields the Monitors whose rotation changed.
+    pub fn rotated(&self) -> impl Iterator<Item = &MonitorChange<'a>> {
+        self.changed.iter().filter(|change| change.is_rotated())
+    }
+
+    /// Yields the Monitors which became or stopped being the primary monitor.
+    pub fn primary_changed(&self) -> impl Iterator<Item = &MonitorChange<'a>> {
+        self.changed
+            .iter()
+            .filter(|change| change.is_primary_changed())
+    }
+
+    /// Returns `true` if both setups have the same Monitors in the same places.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl MonitorSetup {
+    /// Compares this setup with a later one, yielding the Monitors which were added, removed or
+    /// changed. Monitors on the same adapter are paired first, after which displays which moved
+    /// to another port are paired by their identity; a different display plugged into the same
+    /// port counts as a removed and an added Monitor.
+    pub fn diff<'a>(&'a self, other: &'a MonitorSetup) -> SetupDiff<'a> {
+        let keys = |setup: &'a MonitorSetup| -> Vec<OutputKey<'a>> {
+            setup
+                .monitors
+                .iter()
+                .map(|monitor| (monitor.name.as_str(), monitor.identity.as_ref()))
+                .collect()
+        };
+        let pairs = pair_outputs(&keys(other), &keys(self));
+
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+
+        for (&pair, after) in pairs.iter().zip(&other.monitors) {
+            match pair {
+                Some(index) => {
+                    let change = MonitorChange {
+                        before: &self.monitors[index],
+                        after,
+                    };
+
+                    if change.is_changed() {
+                        changed.push(change);
+                    }
+                }
+                None => added.push(after),
+            }
+        }
+
+        let removed = self
+            .monitors
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !pairs.contains(&Some(*index)))
+            .map(|(_, monitor)| monitor)
+            .collect();
+
+        SetupDiff {
+            added,
+            removed,
+            changed,
+        }
+    }
+}
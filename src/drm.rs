@@ -0,0 +1,242 @@
+use crate::{
+    ConnectedOutput, ConnectionState, LoadMonitors, LoadOutputs, Mode, Monitor, MonitorIdentity,
+    OutputInfo, Point, Rect,
+};
+
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// The directory in which the kernel exposes DRM devices and their connectors.
+const SYSFS_DRM_ROOT: &str = "/sys/class/drm";
+
+/// This is an implementor for `LoadMonitors` which reads the connectors exposed by the kernel in
+/// `/sys/class/drm`, and therefore works without any display server, e.g. from a TTY.
+/// Since the kernel does not know how a display server would arrange the outputs, the connected
+/// and enabled outputs are laid out from left to right in their preferred mode, aligned at the top.
+/// CRTCs are not exposed either, so each Monitor's CRTC is its index in that layout.
+pub struct DrmMonitorLoader {
+    root: PathBuf,
+}
+
+/// A connector directory, such as `/sys/class/drm/card0-HDMI-A-1`.
+struct Connector {
+    /// The connector name without the card prefix, e.g. `HDMI-A-1`.
+    name: String,
+    path: PathBuf,
+}
+
+impl Connector {
+    /// Reads a file of the connector, yielding `None` if it does not exist.
+    fn read(&self, file: &str) -> Result<Option<Vec<u8>>, Error> {
+        match std::fs::read(self.path.join(file)) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Returns `true` if the kernel reports a display as connected.
+    fn is_connected(&self) -> Result<bool, Error> {
+        Ok(self
+            .read("status")?
+            .is_some_and(|status| status.trim_ascii() == b"connected"))
+    }
+
+    /// Yields the connection state reported by the kernel; connectors without a `status` file are
+    /// reported as unknown.
+    fn connection(&self) -> Result<ConnectionState, Error> {
+        Ok(
+            match self.read("status")?.as_deref().map(<[u8]>::trim_ascii) {
+                Some(b"connected") => ConnectionState::Connected,
+                Some(b"disconnected") => ConnectionState::Disconnected,
+                _ => ConnectionState::Unknown,
+            },
+        )
+    }
+
+    /// Returns `true` if the kernel reports the connector as driving its display.
+    fn is_enabled(&self) -> Result<bool, Error> {
+        Ok(self
+            .read("enabled")?
+            .is_some_and(|enabled| enabled.trim_ascii() == b"enabled"))
+    }
+
+    /// Yields the identity decoded from the connector's EDID, if it has a valid one; some
+    /// displays and KVM switches provide none.
+    fn identity(&self) -> Result<Option<MonitorIdentity>, Error> {
+        Ok(self
+            .read("edid")?
+            .and_then(|edid| MonitorIdentity::from_edid(&edid).ok()))
+    }
+
+    /// Yields the modes of the display, starting with the preferred mode, which the kernel lists
+    /// first. The kernel lists a resolution once per refresh rate without naming the rate, so
+    /// each resolution is yielded once, with an unknown refresh rate.
+    fn modes(&self) -> Result<Vec<Mode>, Error> {
+        let modes = self.read("modes")?.unwrap_or_default();
+        let mut parsed = Vec::<Mode>::new();
+
+        for mode in String::from_utf8_lossy(&modes)
+            .lines()
+            .filter_map(parse_mode)
+        {
+            if !parsed.contains(&mode) {
+                parsed.push(mode);
+            }
+        }
+
+        if let Some(preferred) = parsed.first_mut() {
+            *preferred = preferred.with_preferred(true);
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Parses a line of a connector's `modes` file, such as `1920x1080` or `1920x1080i`.
+fn parse_mode(line: &str) -> Option<Mode> {
+    let (width, height) = line.trim().split_once('x')?;
+    let (height, interlaced) = match height.strip_suffix('i') {
+        Some(height) => (height, true),
+        None => (height, false),
+    };
+
+    Some(Mode::new(width.parse().ok()?, height.parse().ok()?, 0).with_interlaced(interlaced))
+}
+
+impl DrmMonitorLoader {
+    /// Creates an instance of `DrmMonitorLoader` reading from `/sys/class/drm`, if it exists;
+    /// otherwise, yields an Error.
+    pub fn new() -> Result<DrmMonitorLoader, Error> {
+        Self::in_directory(SYSFS_DRM_ROOT)
+    }
+
+    /// Creates an instance of `DrmMonitorLoader` reading from the given directory, which must
+    /// be laid out like `/sys/class/drm`; this allows testing against a fake sysfs tree.
+    pub fn in_directory(root: impl Into<PathBuf>) -> Result<DrmMonitorLoader, Error> {
+        let root = root.into();
+
+        if root.is_dir() {
+            Ok(DrmMonitorLoader { root })
+        } else {
+            Err(Error::new(
+                ErrorKind::NotFound,
+                format!("{} is not a directory", root.display()),
+            ))
+        }
+    }
+
+    /// Yields the directory from which connectors are read.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Yields the connector directories, sorted by card and connector name.
+    fn connectors(&self) -> Result<Vec<Connector>, Error> {
+        let mut connectors = Vec::new();
+
+        for entry in std::fs::read_dir(&self.root)? {
+            let path = entry?.path();
+
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+
+            // connectors are named `card<N>-<connector>`, unlike the cards themselves
+            let Some((_, name)) = file_name
+                .strip_prefix("card")
+                .and_then(|rest| rest.split_once('-'))
+            else {
+                continue;
+            };
+
+            connectors.push(Connector {
+                name: name.to_owned(),
+                path,
+            });
+        }
+
+        connectors.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(connectors)
+    }
+
+    /// Yields every connected output along with the identity of its display, whether or not the
+    /// output is enabled.
+    /// Output names are the connector names without the card prefix, e.g. `HDMI-A-1` for
+    /// `card0-HDMI-A-1`; note that these may differ from the names used by X11 drivers.
+    pub fn connected_outputs(&self) -> Result<Vec<ConnectedOutput>, Error> {
+        let mut outputs = Vec::new();
+
+        for connector in self.connectors()? {
+            if connector.is_connected()? {
+                outputs.push(ConnectedOutput::new(
+                    connector.name.clone(),
+                    connector.identity()?,
+                ));
+            }
+        }
+
+        Ok(outputs)
+    }
+}
+
+impl LoadMonitors<Error> for DrmMonitorLoader {
+    /// Reads the connected and enabled outputs which report at least one mode, placing them side
+    /// by side in their preferred modes.
+    fn load_monitors(&self) -> Result<Vec<Monitor>, Error> {
+        let mut monitors = Vec::new();
+        let mut x_offset = 0;
+
+        for connector in self.connectors()? {
+            // outputs turned off by the display server, e.g. a closed laptop panel, are not shown
+            if !connector.is_connected()? || !connector.is_enabled()? {
+                continue;
+            }
+
+            let mut modes = connector.modes()?;
+            let Some(preferred) = modes.first_mut() else {
+                continue;
+            };
+            // the Monitor is laid out in its preferred mode
+            *preferred = preferred.with_current(true);
+            let (width, height) = (preferred.width(), preferred.height());
+
+            let rect = Rect::new(width, height, Point::new(x_offset, 0));
+            let mut monitor =
+                Monitor::new(connector.name.clone(), monitors.len() as u32, rect).with_modes(modes);
+
+            if let Some(identity) = connector.identity()? {
+                monitor = monitor.with_identity(identity);
+            }
+
+            monitors.push(monitor);
+            x_offset += width;
+        }
+
+        Ok(monitors)
+    }
+}
+
+impl LoadOutputs<Error> for DrmMonitorLoader {
+    /// Reads every connector along with its connection state, enabled state and the identity of
+    /// its display, and the modes of the display. Since the kernel knows neither the layout nor
+    /// the refresh rates, the outputs have no rectangle, and their modes have no refresh rate.
+    fn load_outputs(&self) -> Result<Vec<OutputInfo>, Error> {
+        let mut outputs = Vec::new();
+
+        for connector in self.connectors()? {
+            let mut output = OutputInfo::new(connector.name.clone(), connector.connection()?)
+                .with_enabled(connector.is_enabled()?)
+                .with_modes(connector.modes()?);
+
+            if let Some(identity) = connector.identity()? {
+                output = output.with_identity(identity);
+            }
+
+            outputs.push(output);
+        }
+
+        Ok(outputs)
+    }
+}
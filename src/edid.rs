@@ -0,0 +1,142 @@
+use crate::{LibResult, MonitorUtilsError};
+
+#[cfg(feature = "serialize")]
+use miniserde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// The fixed header at the start of every EDID.
+const EDID_HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+/// The length of the base EDID block; extension blocks follow it.
+const EDID_BLOCK_LENGTH: usize = 128;
+/// Offsets of the four 18-byte display descriptors in the base block.
+const DESCRIPTOR_OFFSETS: [usize; 4] = [54, 72, 90, 108];
+/// Tag of the display descriptor holding the serial number as text.
+const DESCRIPTOR_SERIAL_STRING: u8 = 0xff;
+/// Tag of the display descriptor holding the product name.
+const DESCRIPTOR_PRODUCT_NAME: u8 = 0xfc;
+
+/// Identifies a physical display through the information in its EDID, regardless of the port it
+/// is plugged into.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MonitorIdentity {
+    /// The three-letter PNP ID of the manufacturer, e.g. `DEL` for Dell.
+    manufacturer: String,
+    /// The manufacturer's product code for the model.
+    product_code: u16,
+    /// The numeric serial number, which is 0 for displays which do not set it.
+    serial_number: u32,
+    /// The product name from the display descriptors, if present.
+    product_name: Option<String>,
+    /// The serial number from the display descriptors, if present.
+    serial_string: Option<String>,
+}
+
+/// Decodes the text of a display descriptor, which is terminated by a newline and padded with
+/// spaces.
+fn descriptor_text(data: &[u8]) -> Option<String> {
+    let text = data.split(|&byte| byte == b'\n').next().unwrap_or(data);
+    let text = String::from_utf8_lossy(text).trim().to_owned();
+
+    (!text.is_empty()).then_some(text)
+}
+
+impl MonitorIdentity {
+    /// Decodes the identity from the raw bytes of an EDID, as found in the `EDID` property shown
+    /// by `xrandr --verbose` or in `/sys/class/drm/*/edid`.
+    pub fn from_edid(edid: &[u8]) -> LibResult<MonitorIdentity> {
+        let invalid = |reason: &str| MonitorUtilsError::InvalidEdid(reason.to_owned());
+
+        let block = edid
+            .get(..EDID_BLOCK_LENGTH)
+            .ok_or_else(|| invalid("shorter than a single block"))?;
+
+        if block[..EDID_HEADER.len()] != EDID_HEADER {
+            return Err(invalid("missing header"));
+        }
+
+        if block.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) != 0 {
+            return Err(invalid("checksum mismatch"));
+        }
+
+        // three 5-bit letters, where 1 is 'A'
+        let packed = u16::from_be_bytes([block[8], block[9]]);
+        let manufacturer = [10, 5, 0]
+            .iter()
+            .map(|shift| match (packed >> shift) & 0x1f {
+                letter @ 1..=26 => Ok((b'A' + letter as u8 - 1) as char),
+                _ => Err(invalid("malformed manufacturer ID")),
+            })
+            .collect::<LibResult<String>>()?;
+
+        let product_code = u16::from_le_bytes([block[10], block[11]]);
+        let serial_number = u32::from_le_bytes([block[12], block[13], block[14], block[15]]);
+
+        let mut product_name = None;
+        let mut serial_string = None;
+
+        for offset in DESCRIPTOR_OFFSETS {
+            let descriptor = &block[offset..offset + 18];
+
+            // display descriptors start with a zero pixel clock, unlike timing descriptors
+            if descriptor[..2] != [0, 0] {
+                continue;
+            }
+
+            match descriptor[3] {
+                DESCRIPTOR_PRODUCT_NAME => product_name = descriptor_text(&descriptor[5..]),
+                DESCRIPTOR_SERIAL_STRING => serial_string = descriptor_text(&descriptor[5..]),
+                _ => (),
+            }
+        }
+
+        Ok(MonitorIdentity {
+            manufacturer,
+            product_code,
+            serial_number,
+            product_name,
+            serial_string,
+        })
+    }
+
+    /// Yields the three-letter PNP ID of the manufacturer, e.g. `DEL` for Dell.
+    pub fn manufacturer(&self) -> &str {
+        &self.manufacturer
+    }
+
+    /// Yields the manufacturer's product code for the model.
+    pub fn product_code(&self) -> u16 {
+        self.product_code
+    }
+
+    /// Yields the numeric serial number, which is 0 for displays which do not set it.
+    pub fn serial_number(&self) -> u32 {
+        self.serial_number
+    }
+
+    /// Yields the product name, e.g. `DELL U2720Q`, if the display reports one.
+    pub fn product_name(&self) -> Option<&str> {
+        self.product_name.as_deref()
+    }
+
+    /// Yields the serial number as text, if the display reports one.
+    pub fn serial_string(&self) -> Option<&str> {
+        self.serial_string.as_deref()
+    }
+}
+
+impl fmt::Display for MonitorIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.product_name {
+            Some(name) => write!(f, "{} {}", self.manufacturer, name)?,
+            None => write!(f, "{} {:04x}", self.manufacturer, self.product_code)?,
+        }
+
+        match &self.serial_string {
+            Some(serial) => write!(f, " ({})", serial),
+            None if self.serial_number != 0 => write!(f, " ({})", self.serial_number),
+            None => Ok(()),
+        }
+    }
+}
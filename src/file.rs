@@ -0,0 +1,204 @@
+use crate::{LoadMonitors, Monitor, Point, Rect, Reflection, Rotation, Scale};
+
+use miniserde::Deserialize;
+
+use std::io::{Error, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+
+/// A layout file, which describes the monitors independently of the way `MonitorSetup` is
+/// serialized, so that it can be written by hand:
+///
+/// ```json
+/// {
+///     "monitors": [
+///         { "name": "eDP-1", "width": 1920, "height": 1080, "x": 0, "y": 0, "primary": true },
+///         { "name": "HDMI-1", "width": 1080, "height": 1920, "x": 1920, "y": 0, "rotation": "left" }
+///     ]
+/// }
+/// ```
+#[derive(Deserialize)]
+struct LayoutFile {
+    monitors: Vec<LayoutMonitor>,
+}
+
+/// A monitor in a layout file. Only the name and geometry are required.
+#[derive(Deserialize)]
+struct LayoutMonitor {
+    name: String,
+    width: u32,
+    height: u32,
+    x: u32,
+    y: u32,
+    primary: Option<bool>,
+    /// One of `normal`, `left`, `inverted` or `right`, as in `xrandr`.
+    rotation: Option<String>,
+    /// One of `normal`, `x`, `y` or `xy`, as in `xrandr`.
+    reflection: Option<String>,
+    crtc: Option<u32>,
+    xinerama_index: Option<u32>,
+    /// The factor by which the monitor's geometry is multiplied to yield physical pixels, e.g.
+    /// `1.25`.
+    scale: Option<f64>,
+}
+
+/// Where the layout is read from.
+enum LayoutSource {
+    Path(PathBuf),
+    /// The layout was read up front, e.g. from stdin, which cannot be read twice.
+    Contents(String),
+}
+
+/// This is an implementor for `LoadMonitors` which reads a hand-written layout from a file or
+/// stdin, rather than querying a display server. This allows running queries in CI, over SSH, or
+/// against the layout of another machine.
+/// Layouts may be written in JSON, or in TOML with the `toml` feature; TOML layouts use a
+/// `[[monitors]]` table for each monitor, with the same keys as in JSON.
+pub struct FileMonitorLoader {
+    source: LayoutSource,
+}
+
+/// Converts a TOML value into the equivalent JSON value, so that both formats can share a single
+/// deserializer.
+#[cfg(feature = "toml")]
+fn toml_to_json(value: toml::Value) -> miniserde::json::Value {
+    use miniserde::json::{Array, Number, Object, Value};
+
+    match value {
+        toml::Value::String(string) => Value::String(string),
+        toml::Value::Integer(integer) if integer >= 0 => Value::Number(Number::U64(integer as u64)),
+        toml::Value::Integer(integer) => Value::Number(Number::I64(integer)),
+        toml::Value::Float(float) => Value::Number(Number::F64(float)),
+        toml::Value::Boolean(boolean) => Value::Bool(boolean),
+        toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        toml::Value::Array(values) => {
+            let mut array = Array::new();
+            array.extend(values.into_iter().map(toml_to_json));
+            Value::Array(array)
+        }
+        toml::Value::Table(table) => {
+            let mut object = Object::new();
+            object.extend(table.into_iter().map(|(k, v)| (k, toml_to_json(v))));
+            Value::Object(object)
+        }
+    }
+}
+
+/// Parses a layout, which is JSON if it starts with `{` and TOML otherwise.
+fn parse_layout(contents: &str) -> Result<LayoutFile, Error> {
+    let invalid = |message: String| Error::new(ErrorKind::InvalidData, message);
+
+    let json = if contents.trim_start().starts_with('{') {
+        contents.to_owned()
+    } else {
+        #[cfg(feature = "toml")]
+        {
+            let table = contents
+                .parse::<toml::Table>()
+                .map_err(|error| invalid(format!("invalid TOML layout: {}", error)))?;
+            miniserde::json::to_string(&toml_to_json(toml::Value::Table(table)))
+        }
+
+        #[cfg(not(feature = "toml"))]
+        return Err(invalid(
+            "layout is not JSON, and TOML support is not enabled".to_owned(),
+        ));
+    };
+
+    // miniserde does not report where parsing failed
+    miniserde::json::from_str(&json).map_err(|_| {
+        invalid(
+            "invalid layout: expected a list of monitors with a name, width, height, x and y"
+                .to_owned(),
+        )
+    })
+}
+
+impl FileMonitorLoader {
+    /// Creates an instance of `FileMonitorLoader` which reads the layout from the given file
+    /// every time the monitors are loaded.
+    pub fn from_path(path: impl Into<PathBuf>) -> FileMonitorLoader {
+        FileMonitorLoader {
+            source: LayoutSource::Path(path.into()),
+        }
+    }
+
+    /// Creates an instance of `FileMonitorLoader` from the given layout.
+    pub fn from_contents(contents: impl Into<String>) -> FileMonitorLoader {
+        FileMonitorLoader {
+            source: LayoutSource::Contents(contents.into()),
+        }
+    }
+
+    /// Creates an instance of `FileMonitorLoader` by reading the layout from stdin until it is
+    /// closed.
+    pub fn from_stdin() -> Result<FileMonitorLoader, Error> {
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents)?;
+
+        Ok(Self::from_contents(contents))
+    }
+
+    /// Yields the file from which the layout is read, if it is not read from stdin or a string.
+    pub fn path(&self) -> Option<&Path> {
+        match &self.source {
+            LayoutSource::Path(path) => Some(path),
+            LayoutSource::Contents(_) => None,
+        }
+    }
+}
+
+impl LoadMonitors<Error> for FileMonitorLoader {
+    /// Reads the monitors from the layout, in the order they are listed.
+    fn load_monitors(&self) -> Result<Vec<Monitor>, Error> {
+        let layout = match &self.source {
+            LayoutSource::Path(path) => parse_layout(&std::fs::read_to_string(path)?)?,
+            LayoutSource::Contents(contents) => parse_layout(contents)?,
+        };
+
+        layout
+            .monitors
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let rect = Rect::new(entry.width, entry.height, Point::new(entry.x, entry.y));
+                let crtc = entry.crtc.unwrap_or(index as u32);
+
+                let rotation = match &entry.rotation {
+                    Some(rotation) => rotation
+                        .parse::<Rotation>()
+                        .map_err(|error| Error::new(ErrorKind::InvalidData, error))?,
+                    None => Rotation::Normal,
+                };
+
+                let reflection = match &entry.reflection {
+                    Some(reflection) => reflection
+                        .parse::<Reflection>()
+                        .map_err(|error| Error::new(ErrorKind::InvalidData, error))?,
+                    None => Reflection::Normal,
+                };
+
+                let scale = match entry.scale {
+                    Some(factor) => Scale::from_f64(factor).ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::InvalidData,
+                            format!("invalid scale: {}; expected a positive factor", factor),
+                        )
+                    })?,
+                    None => Scale::ONE,
+                };
+
+                let mut monitor = Monitor::new(entry.name, crtc, rect)
+                    .with_primary(entry.primary.unwrap_or(false))
+                    .with_rotation(rotation)
+                    .with_reflection(reflection)
+                    .with_scale(scale);
+
+                if let Some(index) = entry.xinerama_index {
+                    monitor = monitor.with_xinerama_index(index);
+                }
+
+                Ok(monitor)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{Monitor, MonitorSetup, Rotation};
+
+use std::fmt;
+
+/// The Coordinate Transformation Matrix of an input device such as a touchscreen or a graphics
+/// tablet, which maps the coordinates reported by the device, normalized to the range 0 to 1,
+/// onto the virtual screen, also normalized to the range 0 to 1.
+/// The matrix is stored in row-major order, which is the order expected by
+/// `xinput set-prop <device> "Coordinate Transformation Matrix"`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct InputMatrix {
+    values: [f64; 9],
+}
+
+impl InputMatrix {
+    /// The matrix which maps the device onto the whole virtual screen, which is the default.
+    pub const IDENTITY: InputMatrix = InputMatrix {
+        values: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+    };
+
+    /// Creates an `InputMatrix` from its values in row-major order.
+    pub fn new(values: [f64; 9]) -> InputMatrix {
+        InputMatrix { values }
+    }
+
+    /// Yields the values of the matrix in row-major order.
+    pub fn values(&self) -> [f64; 9] {
+        self.values
+    }
+
+    /// Applies the matrix to a point in normalized device coordinates, yielding the point in
+    /// normalized screen coordinates.
+    pub fn transform(&self, x: f64, y: f64) -> (f64, f64) {
+        let [a, b, c, d, e, f, ..] = self.values;
+        (a * x + b * y + c, d * x + e * y + f)
+    }
+
+    /// Yields the product of this matrix and `other`, which applies `other` first.
+    fn multiply(&self, other: &InputMatrix) -> InputMatrix {
+        let mut values = [0.0; 9];
+
+        for row in 0..3 {
+            for column in 0..3 {
+                values[row * 3 + column] = (0..3)
+                    .map(|index| self.values[row * 3 + index] * other.values[index * 3 + column])
+                    .sum();
+            }
+        }
+
+        InputMatrix { values }
+    }
+}
+
+impl fmt::Display for InputMatrix {
+    /// Formats the values separated by spaces, as passed to `xinput`, e.g. `0.5 0 0.5 0 1 0 0 0 1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = self
+            .values
+            .iter()
+            // avoid printing -0, which the products of the rotation yield
+            .map(|value| if *value == 0.0 { 0.0 } else { *value })
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>();
+
+        f.write_str(&values.join(" "))
+    }
+}
+
+impl MonitorSetup {
+    /// Yields the smallest virtual screen containing every monitor, as its width and height.
+    /// This is the size X sizes its screen to by default.
+    pub fn screen_size(&self) -> (u32, u32) {
+        self.monitors
+            .iter()
+            .fold((0, 0), |(width, height), monitor| {
+                let rect = &monitor.rect;
+                // saturate, so that monitors reaching past the end of the coordinate space are
+                // clipped
+                (
+                    width.max(rect.offset.x().saturating_add(rect.width)),
+                    height.max(rect.offset.y().saturating_add(rect.height)),
+                )
+            })
+    }
+
+    /// Yields the Coordinate Transformation Matrix which restricts an input device attached to
+    /// the panel of the given monitor, such as its touchscreen, to that monitor. The rotation and
+    /// reflection of the monitor are taken into account, so that the device follows the panel.
+    /// The matrix is relative to the `screen_size` of the setup.
+    pub fn input_matrix(&self, monitor: &Monitor) -> InputMatrix {
+        let (screen_width, screen_height) = self.screen_size();
+        let (screen_width, screen_height) =
+            (screen_width.max(1) as f64, screen_height.max(1) as f64);
+        let rect = &monitor.rect;
+
+        // maps the panel onto the image of the monitor, both normalized, like `from_panel_coords`
+        let rotation = match monitor.rotation {
+            Rotation::Normal => InputMatrix::IDENTITY,
+            Rotation::Left => InputMatrix::new([0.0, -1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0]),
+            Rotation::Inverted => InputMatrix::new([-1.0, 0.0, 1.0, 0.0, -1.0, 1.0, 0.0, 0.0, 1.0]),
+            Rotation::Right => InputMatrix::new([0.0, 1.0, 0.0, -1.0, 0.0, 1.0, 0.0, 0.0, 1.0]),
+        };
+
+        let (flip_x, flip_y) = (
+            monitor.reflection().reflects_x(),
+            monitor.reflection().reflects_y(),
+        );
+        let reflection = InputMatrix::new([
+            if flip_x { -1.0 } else { 1.0 },
+            0.0,
+            if flip_x { 1.0 } else { 0.0 },
+            0.0,
+            if flip_y { -1.0 } else { 1.0 },
+            if flip_y { 1.0 } else { 0.0 },
+            0.0,
+            0.0,
+            1.0,
+        ]);
+
+        // maps the image of the monitor onto its rectangle within the virtual screen
+        let placement = InputMatrix::new([
+            rect.width as f64 / screen_width,
+            0.0,
+            rect.offset.x() as f64 / screen_width,
+            0.0,
+            rect.height as f64 / screen_height,
+            rect.offset.y() as f64 / screen_height,
+            0.0,
+            0.0,
+            1.0,
+        ]);
+
+        placement.multiply(&reflection).multiply(&rotation)
+    }
+}
@@ -0,0 +1,330 @@
+use crate::{ConnectedOutput, Mode, Monitor, MonitorIdentity, Rect, Reflection, Rotation, Scale};
+
+use std::fmt;
+
+/// Whether a display is connected to an output.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ConnectionState {
+    Connected,
+    Disconnected,
+    /// The driver cannot detect whether a display is connected, as with some VGA ports. Such
+    /// outputs are treated as connected.
+    Unknown,
+}
+
+impl ConnectionState {
+    /// Yields the name of the state, as used by `xrandr`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConnectionState::Connected => "connected",
+            ConnectionState::Disconnected => "disconnected",
+            ConnectionState::Unknown => "unknown connection",
+        }
+    }
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// An output (or connector) of the graphics card, whether or not a display is connected to it and
+/// whether or not it is part of the layout.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OutputInfo {
+    /// The name of the adapter.
+    name: String,
+    /// Whether a display is connected to the output.
+    connection: ConnectionState,
+    /// Whether the output is driving its display.
+    enabled: bool,
+    /// The rectangle displayed by the output within the virtual screen, if it is enabled and the
+    /// loader knows the layout.
+    rect: Option<Rect>,
+    /// Whether the output is the primary output of the display.
+    primary: bool,
+    /// The rotation of the output's image.
+    rotation: Rotation,
+    /// The reflection of the output's image, applied after the rotation.
+    reflection: Reflection,
+    /// The factor by which the rectangle is multiplied to yield physical pixels.
+    scale: Scale,
+    /// The CRTC driving the output, if any.
+    crtc: Option<u32>,
+    /// The CRTCs which are able to drive the output.
+    possible_crtcs: Vec<u32>,
+    /// The rotations supported by the output.
+    rotations: Vec<Rotation>,
+    /// The physical width and height of the display in millimeters, if known.
+    physical_size: Option<(u32, u32)>,
+    /// The identity of the connected display, decoded from its EDID if available.
+    identity: Option<MonitorIdentity>,
+    /// The modes supported by the connected display, in the order reported by the loader.
+    modes: Vec<Mode>,
+}
+
+impl OutputInfo {
+    /// Creates a new `OutputInfo` for an output which is disabled.
+    pub fn new(name: String, connection: ConnectionState) -> OutputInfo {
+        OutputInfo {
+            name,
+            connection,
+            enabled: false,
+            rect: None,
+            primary: false,
+            rotation: Rotation::Normal,
+            reflection: Reflection::Normal,
+            scale: Scale::ONE,
+            crtc: None,
+            possible_crtcs: vec![],
+            rotations: vec![],
+            physical_size: None,
+            identity: None,
+            modes: vec![],
+        }
+    }
+
+    /// Marks the output as enabled, displaying the given rectangle of the virtual screen.
+    pub fn with_rect(mut self, rect: Rect) -> OutputInfo {
+        self.enabled = true;
+        self.rect = Some(rect);
+        self
+    }
+
+    /// Marks the output as enabled or disabled, for loaders which do not know the layout.
+    pub fn with_enabled(mut self, enabled: bool) -> OutputInfo {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Marks the output as the primary output of the display, or not.
+    pub fn with_primary(mut self, primary: bool) -> OutputInfo {
+        self.primary = primary;
+        self
+    }
+
+    /// Sets the rotation of the output's image.
+    pub fn with_rotation(mut self, rotation: Rotation) -> OutputInfo {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Sets the reflection of the output's image.
+    pub fn with_reflection(mut self, reflection: Reflection) -> OutputInfo {
+        self.reflection = reflection;
+        self
+    }
+
+    /// Sets the factor by which the rectangle is multiplied to yield physical pixels.
+    pub fn with_scale(mut self, scale: Scale) -> OutputInfo {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the CRTC driving the output.
+    pub fn with_crtc(mut self, crtc: u32) -> OutputInfo {
+        self.crtc = Some(crtc);
+        self
+    }
+
+    /// Sets the CRTCs which are able to drive the output.
+    pub fn with_possible_crtcs(mut self, possible_crtcs: Vec<u32>) -> OutputInfo {
+        self.possible_crtcs = possible_crtcs;
+        self
+    }
+
+    /// Sets the rotations supported by the output.
+    pub fn with_rotations(mut self, rotations: Vec<Rotation>) -> OutputInfo {
+        self.rotations = rotations;
+        self
+    }
+
+    /// Sets the physical width and height of the display in millimeters.
+    pub fn with_physical_size(mut self, width: u32, height: u32) -> OutputInfo {
+        self.physical_size = Some((width, height));
+        self
+    }
+
+    /// Sets the identity of the connected display.
+    pub fn with_identity(mut self, identity: MonitorIdentity) -> OutputInfo {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Sets the modes supported by the connected display.
+    pub fn with_modes(mut self, modes: Vec<Mode>) -> OutputInfo {
+        self.modes = modes;
+        self
+    }
+
+    /// Yields the name of the adapter.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Yields whether a display is connected to the output.
+    pub fn connection(&self) -> ConnectionState {
+        self.connection
+    }
+
+    /// Returns `true` if a display is connected to the output, or if the driver cannot tell.
+    pub fn is_connected(&self) -> bool {
+        self.connection != ConnectionState::Disconnected
+    }
+
+    /// Returns `true` if the output is driving its display.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Yields the rectangle displayed by the output, if it is enabled and the loader knows the
+    /// layout.
+    pub fn rect(&self) -> Option<&Rect> {
+        self.rect.as_ref()
+    }
+
+    /// Returns `true` if the output is the primary output of the display.
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+
+    /// Yields the rotation of the output's image.
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Yields the reflection of the output's image, which is applied after the rotation.
+    pub fn reflection(&self) -> Reflection {
+        self.reflection
+    }
+
+    /// Yields the factor by which the rectangle is multiplied to yield physical pixels.
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    /// Yields the CRTC driving the output, if any.
+    pub fn crtc(&self) -> Option<u32> {
+        self.crtc
+    }
+
+    /// Yields the CRTCs which are able to drive the output.
+    pub fn possible_crtcs(&self) -> &[u32] {
+        &self.possible_crtcs
+    }
+
+    /// Yields the rotations supported by the output, which is empty if the loader does not report
+    /// them.
+    pub fn rotations(&self) -> &[Rotation] {
+        &self.rotations
+    }
+
+    /// Yields the physical width and height of the display in millimeters, if known.
+    pub fn physical_size(&self) -> Option<(u32, u32)> {
+        self.physical_size
+    }
+
+    /// Yields the identity of the connected display, if available.
+    pub fn identity(&self) -> Option<&MonitorIdentity> {
+        self.identity.as_ref()
+    }
+
+    /// Yields the modes supported by the connected display, which is empty if the loader does not
+    /// report them.
+    pub fn modes(&self) -> &[Mode] {
+        &self.modes
+    }
+
+    /// Yields the mode the connected display prefers, which is usually its native resolution.
+    pub fn preferred_mode(&self) -> Option<&Mode> {
+        self.modes.iter().find(|mode| mode.is_preferred())
+    }
+
+    /// Yields the `Monitor` displayed by the output, if it is connected and enabled, and the loader
+    /// knows the layout.
+    pub fn to_monitor(&self) -> Option<Monitor> {
+        let rect = self.rect.clone().filter(|_| self.is_connected())?;
+
+        let mut monitor = Monitor::new(self.name.clone(), self.crtc.unwrap_or(0), rect)
+            .with_primary(self.primary)
+            .with_rotation(self.rotation)
+            .with_reflection(self.reflection)
+            .with_scale(self.scale)
+            .with_modes(self.modes.clone());
+
+        if let Some(identity) = &self.identity {
+            monitor = monitor.with_identity(identity.clone());
+        }
+
+        Some(monitor)
+    }
+
+    /// Yields the output as a `ConnectedOutput`, if it is connected.
+    pub fn to_connected_output(&self) -> Option<ConnectedOutput> {
+        self.is_connected()
+            .then(|| ConnectedOutput::new(self.name.clone(), self.identity.clone()))
+    }
+}
+
+/// Every output of the graphics card, including outputs without a display and displays which are
+/// turned off. Unlike a `MonitorSetup`, which describes the active layout, this describes what
+/// could be part of a layout.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct OutputInventory {
+    outputs: Vec<OutputInfo>,
+}
+
+impl OutputInventory {
+    /// Creates an `OutputInventory` from the given outputs.
+    pub fn new(outputs: Vec<OutputInfo>) -> OutputInventory {
+        OutputInventory { outputs }
+    }
+
+    /// Given an implementor of `LoadOutputs`, yields an `OutputInventory`.
+    pub fn with_loader<E>(loader: impl LoadOutputs<E>) -> Result<OutputInventory, E> {
+        Ok(OutputInventory::new(loader.load_outputs()?))
+    }
+
+    /// Yields every output, in the order reported by the loader.
+    pub fn outputs(&self) -> &[OutputInfo] {
+        &self.outputs
+    }
+
+    /// Yields the output with the given adapter name.
+    pub fn output_by_name(&self, name: &str) -> Option<&OutputInfo> {
+        self.outputs.iter().find(|output| output.name == name)
+    }
+
+    /// Yields the outputs which have a display connected to them.
+    pub fn connected(&self) -> impl Iterator<Item = &OutputInfo> {
+        self.outputs.iter().filter(|output| output.is_connected())
+    }
+
+    /// Yields the outputs which have a display connected to them, but are turned off.
+    pub fn disabled(&self) -> impl Iterator<Item = &OutputInfo> {
+        self.connected().filter(|output| !output.is_enabled())
+    }
+
+    /// Yields the connected outputs along with the identities of their displays, e.g. for
+    /// `Profiles::detect`.
+    pub fn connected_outputs(&self) -> Vec<ConnectedOutput> {
+        self.outputs
+            .iter()
+            .filter_map(OutputInfo::to_connected_output)
+            .collect()
+    }
+}
+
+/// Trait which abstracts listing every output from the respective environment, like
+/// `LoadMonitors` does for the active monitors.
+pub trait LoadOutputs<E> {
+    fn load_outputs(&self) -> Result<Vec<OutputInfo>, E>;
+}
+
+/// Allows passing a loader by reference, so that it can be reused or inspected afterwards.
+impl<E, L: LoadOutputs<E> + ?Sized> LoadOutputs<E> for &L {
+    fn load_outputs(&self) -> Result<Vec<OutputInfo>, E> {
+        (**self).load_outputs()
+    }
+}
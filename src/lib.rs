@@ -1,6 +1,51 @@
 #[cfg(feature = "x11")]
 pub mod x11;
 
+pub mod drm;
+
+#[cfg(feature = "serialize")]
+pub mod file;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(feature = "wayland")]
+pub mod wayland;
+
+mod adjacency;
+pub use adjacency::{Adjacency, AdjacencyGraph, SharedEdge, Side};
+
+mod validation;
+pub use validation::{LayoutIssue, ValidationReport};
+
+mod diff;
+pub use diff::{MonitorChange, SetupDiff};
+
+mod pairing;
+
+mod edid;
+pub use edid::MonitorIdentity;
+
+mod mode;
+pub use mode::Mode;
+
+mod scale;
+pub use scale::{CoordinateSpace, Scale};
+
+mod input;
+pub use input::InputMatrix;
+
+mod inventory;
+pub use inventory::{ConnectionState, LoadOutputs, OutputInfo, OutputInventory};
+
+mod traversal;
+pub use traversal::TraversalOrder;
+
+#[cfg(feature = "global-cache")]
+mod profile;
+#[cfg(feature = "global-cache")]
+pub use profile::Profiles;
+
 #[cfg(feature = "serialize")]
 use miniserde::{Deserialize, Serialize};
 
@@ -10,6 +55,8 @@ use directories::ProjectDirs;
 #[cfg(feature = "global-cache")]
 use std::path::PathBuf;
 
+use std::str::FromStr;
+
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -17,9 +64,38 @@ pub enum MonitorUtilsError {
     #[error("point out of bounds: {:?}", .0)]
     PointOutOfBounds(Point),
 
+    #[error("rect does not overlap any monitor: {:?}", .0)]
+    RectOutOfBounds(Rect),
+
+    #[error("no monitor adjacent to the {:?} side of {}", .side, .name)]
+    NoAdjacentMonitor { name: String, side: Side },
+
+    #[error("no monitor with xinerama index {}", .0)]
+    UnknownXineramaIndex(u32),
+
+    #[error("invalid geometry {:?}: {}; expected WxH+X+Y", .geometry, .reason)]
+    InvalidGeometry { geometry: String, reason: String },
+
+    #[error("invalid EDID: {}", .0)]
+    InvalidEdid(String),
+
+    #[error("invalid rotation {:?}; expected normal, left, inverted or right", .0)]
+    InvalidRotation(String),
+
+    #[error("invalid reflection {:?}; expected normal, x, y or xy", .0)]
+    InvalidReflection(String),
+
     #[error("monitor setup is invalid")]
     InvalidMonitorSetup,
 
+    #[cfg(feature = "global-cache")]
+    #[error("no profile named {}", .0)]
+    UnknownProfile(String),
+
+    #[cfg(feature = "global-cache")]
+    #[error("invalid profile name: {:?}", .0)]
+    InvalidProfileName(String),
+
     #[cfg(feature = "global-cache")]
     #[error("failed to read/write cache file")]
     ReadWriteCache {
@@ -86,15 +162,24 @@ pub struct Rect {
 }
 
 impl Rect {
+    /// Creates a new `Rect` with the given dimensions, whose top-left point lies at `offset`.
+    pub fn new(width: u32, height: u32, offset: Point) -> Rect {
+        Rect {
+            width,
+            height,
+            offset,
+        }
+    }
+
     /// Returns `true` if the point lies on the Rectangle, otherwise false.
     fn contains_point(&self, point: &Point) -> bool {
         let offset = self.offset;
 
         let x_min = offset.x();
-        let x_max = x_min + self.width;
+        let x_max = x_min.saturating_add(self.width);
 
         let y_min = offset.y();
-        let y_max = y_min + self.height;
+        let y_max = y_min.saturating_add(self.height);
 
         (point.x() >= x_min && point.x() < x_max) && (point.y() >= y_min && point.y() < y_max)
     }
@@ -122,19 +207,173 @@ impl Rect {
     }
 
     /// Returns `true` if the Rectangle is "empty", otherwise `false`.
-    /// The definition of `empty` still has to be defined.
-    fn is_empty(&self) -> bool {
-        todo!()
+    /// A Rectangle is empty if it has either zero width or zero height, meaning that it covers no
+    /// pixels.
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
     }
 
     /// Yields a Rectangle representing the intersection between the two input Rectangles.
-    fn intersection(&self, other: &Self) -> Self {
-        todo!()
+    /// If the Rectangles do not overlap, the result is empty.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let x_min = self.offset.x().max(other.offset.x());
+        let y_min = self.offset.y().max(other.offset.y());
+
+        // saturate, so that Rectangles reaching past the end of the coordinate space are clipped
+        let x_max = self.offset.x().saturating_add(self.width);
+        let x_max = x_max.min(other.offset.x().saturating_add(other.width));
+        let y_max = self.offset.y().saturating_add(self.height);
+        let y_max = y_max.min(other.offset.y().saturating_add(other.height));
+
+        Rect {
+            width: x_max.saturating_sub(x_min),
+            height: y_max.saturating_sub(y_min),
+            offset: Point::new(x_min, y_min),
+        }
     }
 
     /// Yields the (unsigned) area of the Rectangle.
-    fn area(&self) -> u32 {
-        self.width * self.height
+    /// The area is widened to a `u64`, since the product of two `u32` dimensions may overflow.
+    pub fn area(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+}
+
+impl FromStr for Rect {
+    type Err = MonitorUtilsError;
+
+    /// Parses a geometry of the form `WxH+X+Y`, as printed by `xrandr`, e.g. `1920x1080+0+0`.
+    fn from_str(geometry: &str) -> LibResult<Rect> {
+        let invalid = |reason: String| MonitorUtilsError::InvalidGeometry {
+            geometry: geometry.to_owned(),
+            reason,
+        };
+
+        // `u32::from_str` also accepts a leading '+', which would make e.g. "1x1+0++1" valid
+        let parse_number = |number: &str, what: &str| {
+            if number.is_empty() || !number.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(invalid(format!("{} {:?} is not a number", what, number)));
+            }
+
+            number
+                .parse::<u32>()
+                .map_err(|_| invalid(format!("{} {} is too large", what, number)))
+        };
+
+        let (size, offset) = geometry
+            .split_once('+')
+            .ok_or_else(|| invalid("missing offset".to_owned()))?;
+        let (width, height) = size
+            .split_once('x')
+            .ok_or_else(|| invalid("missing 'x' between width and height".to_owned()))?;
+        let (x, y) = offset
+            .split_once('+')
+            .ok_or_else(|| invalid("missing y offset".to_owned()))?;
+
+        Ok(Rect {
+            width: parse_number(width, "width")?,
+            height: parse_number(height, "height")?,
+            offset: Point::new(parse_number(x, "x offset")?, parse_number(y, "y offset")?),
+        })
+    }
+}
+
+/// The rotation of a Monitor's image, as set by e.g. `xrandr --rotate`.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Rotation {
+    #[default]
+    Normal,
+    /// Rotated 90 degrees counter-clockwise.
+    Left,
+    /// Rotated 180 degrees.
+    Inverted,
+    /// Rotated 90 degrees clockwise.
+    Right,
+}
+
+impl Rotation {
+    /// Yields the name of the rotation as understood by `xrandr --rotate`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rotation::Normal => "normal",
+            Rotation::Left => "left",
+            Rotation::Inverted => "inverted",
+            Rotation::Right => "right",
+        }
+    }
+
+    /// Returns `true` if the rotation swaps the width and height of the Monitor.
+    pub fn is_sideways(&self) -> bool {
+        matches!(self, Rotation::Left | Rotation::Right)
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = MonitorUtilsError;
+
+    /// Parses the name of a rotation, as yielded by `Rotation::name`.
+    fn from_str(rotation: &str) -> LibResult<Rotation> {
+        match rotation {
+            "normal" => Ok(Rotation::Normal),
+            "left" => Ok(Rotation::Left),
+            "inverted" => Ok(Rotation::Inverted),
+            "right" => Ok(Rotation::Right),
+            _ => Err(MonitorUtilsError::InvalidRotation(rotation.to_owned())),
+        }
+    }
+}
+
+/// The reflection of a Monitor's image, as set by e.g. `xrandr --reflect`. The image is reflected
+/// after being rotated, so the axes are those of the virtual screen.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Reflection {
+    #[default]
+    Normal,
+    /// Mirrored horizontally, i.e. across the Y axis.
+    X,
+    /// Mirrored vertically, i.e. across the X axis.
+    Y,
+    /// Mirrored both horizontally and vertically, which looks the same as `Rotation::Inverted`.
+    XY,
+}
+
+impl Reflection {
+    /// Yields the name of the reflection as understood by `xrandr --reflect`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Reflection::Normal => "normal",
+            Reflection::X => "x",
+            Reflection::Y => "y",
+            Reflection::XY => "xy",
+        }
+    }
+
+    /// Returns `true` if the reflection flips x coordinates.
+    pub fn reflects_x(&self) -> bool {
+        matches!(self, Reflection::X | Reflection::XY)
+    }
+
+    /// Returns `true` if the reflection flips y coordinates.
+    pub fn reflects_y(&self) -> bool {
+        matches!(self, Reflection::Y | Reflection::XY)
+    }
+}
+
+impl FromStr for Reflection {
+    type Err = MonitorUtilsError;
+
+    /// Parses the name of a reflection, as yielded by `Reflection::name`, or as printed by
+    /// `xrandr`, e.g. `X axis`.
+    fn from_str(reflection: &str) -> LibResult<Reflection> {
+        match reflection {
+            "normal" => Ok(Reflection::Normal),
+            "x" | "X axis" => Ok(Reflection::X),
+            "y" | "Y axis" => Ok(Reflection::Y),
+            "xy" | "X and Y axis" => Ok(Reflection::XY),
+            _ => Err(MonitorUtilsError::InvalidReflection(reflection.to_owned())),
+        }
     }
 }
 
@@ -142,16 +381,39 @@ impl Rect {
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Monitor {
-    /// The index of the Monitor in a clock-wise ordering of its parent `MonitorSetup`
+    /// The index of the Monitor in the traversal order of its parent `MonitorSetup`, which is
+    /// clock-wise by default
     order: u32,
 
     /// The name of the adapter corresponding to the monitor.
     name: String,
     /// CRTC index, used internally by graphics cards.
     crtc: u32,
-
-    /// Rectangle representing the Monitor within the virtual screen.
+    /// Whether the Monitor is the primary monitor of the display.
+    primary: bool,
+    /// The index of the Monitor in the list of Xinerama screens, which legacy tools use to select
+    /// monitors. This is derived by the `MonitorSetup` if the loader does not provide it.
+    xinerama_index: Option<u32>,
+    /// The rotation of the Monitor's image.
+    rotation: Rotation,
+    /// The reflection of the Monitor's image, applied after the rotation. This is `None` if the
+    /// image is not reflected, as in layouts saved before reflections were recorded.
+    reflection: Option<Reflection>,
+    /// The identity of the physical display, decoded from its EDID if available.
+    identity: Option<MonitorIdentity>,
+    /// The modes supported by the display, in the order reported by the loader. This is `None` if
+    /// the loader does not report modes, as in layouts saved before modes were recorded.
+    modes: Option<Vec<Mode>>,
+    /// The factor by which the logical Rectangle is multiplied to yield physical pixels. This is
+    /// `None` for a factor of 1, as in layouts saved before scales were recorded.
+    scale: Option<Scale>,
+
+    /// Rectangle representing the Monitor within the virtual screen, in logical coordinates.
     pub rect: Rect,
+
+    /// Names of the other adapters displaying the exact same Rectangle, which are grouped into
+    /// this Monitor when it is part of a `MonitorSetup`.
+    mirrors: Vec<String>,
 }
 
 impl Monitor {
@@ -163,10 +425,63 @@ impl Monitor {
 
             name,
             crtc,
+            primary: false,
+            xinerama_index: None,
+            rotation: Rotation::Normal,
+            reflection: None,
+            identity: None,
+            modes: None,
+            scale: None,
             rect,
+
+            mirrors: vec![],
         }
     }
 
+    /// Marks the Monitor as the primary monitor of the display, or not.
+    pub fn with_primary(mut self, primary: bool) -> Monitor {
+        self.primary = primary;
+        self
+    }
+
+    /// Sets the index of the Monitor in the list of Xinerama screens.
+    pub fn with_xinerama_index(mut self, xinerama_index: u32) -> Monitor {
+        self.xinerama_index = Some(xinerama_index);
+        self
+    }
+
+    /// Sets the rotation of the Monitor's image. The `rect` of the Monitor must already account
+    /// for the rotation, i.e. a sideways 1920x1080 panel has a 1080x1920 `rect`.
+    pub fn with_rotation(mut self, rotation: Rotation) -> Monitor {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Sets the reflection of the Monitor's image.
+    pub fn with_reflection(mut self, reflection: Reflection) -> Monitor {
+        self.reflection = (reflection != Reflection::Normal).then_some(reflection);
+        self
+    }
+
+    /// Sets the identity of the physical display.
+    pub fn with_identity(mut self, identity: MonitorIdentity) -> Monitor {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Sets the modes supported by the display.
+    pub fn with_modes(mut self, modes: Vec<Mode>) -> Monitor {
+        self.modes = (!modes.is_empty()).then_some(modes);
+        self
+    }
+
+    /// Sets the factor by which the Monitor's logical Rectangle is multiplied to yield physical
+    /// pixels.
+    pub fn with_scale(mut self, scale: Scale) -> Monitor {
+        self.scale = (scale != Scale::ONE).then_some(scale);
+        self
+    }
+
     /// Yields the name of the adapter associated with the Monitor.
     pub fn name(&self) -> &str {
         &self.name
@@ -177,10 +492,292 @@ impl Monitor {
         self.crtc
     }
 
-    /// Yields the rectangle representing the Monitor within the virtual screen.
+    /// Yields the rectangle representing the Monitor within the virtual screen, in logical
+    /// coordinates.
     pub fn rect(&self) -> &Rect {
         &self.rect
     }
+
+    /// Yields the factor by which the Monitor's logical Rectangle is multiplied to yield physical
+    /// pixels.
+    pub fn scale(&self) -> Scale {
+        self.scale.unwrap_or(Scale::ONE)
+    }
+
+    /// Returns `true` if the Monitor is the primary monitor of the display.
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+
+    /// Yields the rotation of the Monitor's image.
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Yields the reflection of the Monitor's image, which is applied after the rotation.
+    pub fn reflection(&self) -> Reflection {
+        self.reflection.unwrap_or_default()
+    }
+
+    /// Yields the identity of the physical display, which unlike the adapter name does not depend
+    /// on the port the display is plugged into.
+    pub fn identity(&self) -> Option<&MonitorIdentity> {
+        self.identity.as_ref()
+    }
+
+    /// Yields the modes supported by the display, which is empty if the loader does not report
+    /// them.
+    pub fn modes(&self) -> &[Mode] {
+        self.modes.as_deref().unwrap_or_default()
+    }
+
+    /// Yields the mode the display prefers, which is usually its native resolution.
+    pub fn preferred_mode(&self) -> Option<&Mode> {
+        self.modes().iter().find(|mode| mode.is_preferred())
+    }
+
+    /// Yields the mode the Monitor is currently running in.
+    pub fn current_mode(&self) -> Option<&Mode> {
+        self.modes().iter().find(|mode| mode.is_current())
+    }
+
+    /// Yields the index of the Monitor in the list of Xinerama screens, as used by tools like
+    /// `dmenu -m` and `rofi -m`.
+    pub fn xinerama_index(&self) -> Option<u32> {
+        self.xinerama_index
+    }
+
+    /// Yields the names of the other adapters mirroring this Monitor.
+    pub fn mirrors(&self) -> &[String] {
+        &self.mirrors
+    }
+
+    /// Returns `true` if other adapters are mirroring this Monitor.
+    pub fn is_mirrored(&self) -> bool {
+        !self.mirrors.is_empty()
+    }
+
+    /// Yields the names of every adapter displaying this Monitor, starting with its own name.
+    pub fn members(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.mirrors.iter().map(String::as_str))
+    }
+
+    /// Yields the size of the Monitor's image in physical pixels, after rotation.
+    fn physical_size(&self) -> (u32, u32) {
+        (
+            self.scale().to_physical(self.rect.width),
+            self.scale().to_physical(self.rect.height),
+        )
+    }
+
+    /// Converts a logical point within the Monitor, relative to its top-left corner, to the
+    /// physical pixel displaying it, relative to the top-left corner of its physical image.
+    fn to_physical_relative(&self, relative: &Point) -> Point {
+        let (width, height) = self.physical_size();
+        let physical = self.scale().to_physical_point(relative);
+
+        // scaling rounds up, so the last logical pixel of a Monitor scaled by less than 1 may
+        // yield a coordinate just past its last physical pixel
+        Point::new(
+            physical.x().min(width.saturating_sub(1)),
+            physical.y().min(height.saturating_sub(1)),
+        )
+    }
+
+    /// Yields the width and height of the panel in physical pixels, before rotation, i.e. in the
+    /// orientation in which the display scans out its image.
+    pub fn panel_size(&self) -> (u32, u32) {
+        let (width, height) = self.physical_size();
+
+        if self.rotation.is_sideways() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Converts a point within the virtual screen, in logical coordinates, to the physical pixel of
+    /// the panel displaying it, relative to the top-left corner of the panel before rotation and
+    /// reflection. This is e.g. the pixel in a raw framebuffer dump, or the position reported by a
+    /// touchscreen mounted on the panel.
+    pub fn to_panel_coords(&self, point: &Point) -> LibResult<Point> {
+        if !self.rect.contains_point(point) {
+            return Err(MonitorUtilsError::PointOutOfBounds(*point));
+        }
+
+        let (width, height) = self.physical_size();
+        let relative = self.to_physical_relative(&Point::new(
+            point.x() - self.rect.offset.x(),
+            point.y() - self.rect.offset.y(),
+        ));
+
+        // undo the reflection, which is applied last, then the rotation
+        let x = if self.reflection().reflects_x() {
+            width - 1 - relative.x()
+        } else {
+            relative.x()
+        };
+        let y = if self.reflection().reflects_y() {
+            height - 1 - relative.y()
+        } else {
+            relative.y()
+        };
+
+        Ok(match self.rotation {
+            Rotation::Normal => Point::new(x, y),
+            // the top edge of the panel is on the right of the image
+            Rotation::Left => Point::new(y, width - 1 - x),
+            Rotation::Inverted => Point::new(width - 1 - x, height - 1 - y),
+            // the top edge of the panel is on the left of the image
+            Rotation::Right => Point::new(height - 1 - y, x),
+        })
+    }
+
+    /// Converts a physical pixel of the panel, relative to its top-left corner before rotation and
+    /// reflection, to the point it displays within the virtual screen, in logical coordinates.
+    /// This is the inverse of `to_panel_coords`.
+    pub fn from_panel_coords(&self, point: &Point) -> LibResult<Point> {
+        let (panel_width, panel_height) = self.panel_size();
+        if point.x() >= panel_width || point.y() >= panel_height {
+            return Err(MonitorUtilsError::PointOutOfBounds(*point));
+        }
+
+        let (width, height) = self.physical_size();
+        let (x, y) = match self.rotation {
+            Rotation::Normal => (point.x(), point.y()),
+            Rotation::Left => (width - 1 - point.y(), point.x()),
+            Rotation::Inverted => (width - 1 - point.x(), height - 1 - point.y()),
+            Rotation::Right => (point.y(), height - 1 - point.x()),
+        };
+
+        let x = if self.reflection().reflects_x() {
+            width - 1 - x
+        } else {
+            x
+        };
+        let y = if self.reflection().reflects_y() {
+            height - 1 - y
+        } else {
+            y
+        };
+
+        Ok(self.rect.offset + self.scale().to_logical_point(&Point::new(x, y)))
+    }
+}
+
+/// An output which has a display connected to it, whether or not the display is currently part of
+/// the layout.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConnectedOutput {
+    /// The name of the adapter.
+    name: String,
+    /// The identity of the connected display, decoded from its EDID if available.
+    identity: Option<MonitorIdentity>,
+}
+
+impl ConnectedOutput {
+    /// Creates a new `ConnectedOutput`.
+    pub fn new(name: String, identity: Option<MonitorIdentity>) -> ConnectedOutput {
+        ConnectedOutput { name, identity }
+    }
+
+    /// Yields the name of the adapter.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Yields the identity of the connected display, if available.
+    pub fn identity(&self) -> Option<&MonitorIdentity> {
+        self.identity.as_ref()
+    }
+}
+
+/// Describes how a `Rect` is transformed when it is mapped from one `Monitor` onto another.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MapMode {
+    /// Keeps the size of the Rectangle in pixels, along with its offset from the top-left corner
+    /// of the Monitor. The Rectangle is shrunk and shifted only as far as needed to fit on the
+    /// target Monitor.
+    PreservePixels,
+    /// Scales the size and offset of the Rectangle by the ratio between the dimensions of the two
+    /// Monitors, such that it covers the same fraction of the target Monitor.
+    PreserveRelative,
+    /// Behaves like `PreservePixels`, except along axes on which the Rectangle spans the entire
+    /// source Monitor; there, it spans the entire target Monitor instead. This keeps maximized
+    /// and half-tiled windows maximized.
+    PreserveMaximized,
+}
+
+/// Maps a segment `[start, start + length)` along a single axis from the `from` segment onto the
+/// `to` segment, yielding the new start and length.
+fn map_segment(
+    (start, length): (u32, u32),
+    (from_start, from_length): (u32, u32),
+    (to_start, to_length): (u32, u32),
+    mode: MapMode,
+) -> (u32, u32) {
+    // offset of the segment from the start of the source, which may be negative
+    let relative_start = start as i64 - from_start as i64;
+    let spans_source = relative_start <= 0
+        && start as i64 + length as i64 >= from_start as i64 + from_length as i64;
+
+    let (new_start, new_length) = match mode {
+        MapMode::PreserveMaximized if spans_source => (to_start as i64, to_length as i64),
+        MapMode::PreservePixels | MapMode::PreserveMaximized => {
+            let new_length = length.min(to_length) as i64;
+            let max_offset = to_length as i64 - new_length;
+
+            (
+                to_start as i64 + relative_start.clamp(0, max_offset),
+                new_length,
+            )
+        }
+        MapMode::PreserveRelative => {
+            let scale = |value: i64| {
+                if from_length == 0 {
+                    0
+                } else {
+                    value * to_length as i64 / from_length as i64
+                }
+            };
+
+            (
+                to_start as i64 + scale(relative_start),
+                scale(length as i64),
+            )
+        }
+    };
+
+    let clamp = |value: i64| value.clamp(0, u32::MAX as i64) as u32;
+
+    (clamp(new_start), clamp(new_length))
+}
+
+/// Describes how much of a `Rect` lies on a particular `Monitor`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MonitorOverlap<'a> {
+    monitor: &'a Monitor,
+    /// Area of the intersection between the Rectangle and the Monitor.
+    area: u64,
+    /// Fraction of the Rectangle's area which lies on the Monitor, between 0 and 1.
+    fraction: f64,
+}
+
+impl<'a> MonitorOverlap<'a> {
+    /// Yields the Monitor that this overlap refers to.
+    pub fn monitor(&self) -> &'a Monitor {
+        self.monitor
+    }
+
+    /// Yields the area of the intersection between the Rectangle and the Monitor.
+    pub fn area(&self) -> u64 {
+        self.area
+    }
+
+    /// Yields the fraction of the Rectangle's area which lies on the Monitor, between 0 and 1.
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
 }
 
 /// A `MonitorSetup` represents a group of monitors used in conjunction with one another.
@@ -217,7 +814,7 @@ impl MonitorSetup {
         let cache_file = Self::get_cache_file();
         let string = std::fs::read_to_string(cache_file)?;
 
-        Self::from_json(&string).map_err(|e| e.into())
+        Self::from_json(&string)
     }
 
     #[cfg(feature = "global-cache")]
@@ -228,13 +825,100 @@ impl MonitorSetup {
         Ok(())
     }
 
+    /// Creates a `MonitorSetup` from the given monitors directly, without going through a
+    /// loader. The monitors are processed in the same way as by `reload`.
+    pub fn new(monitors: Vec<Monitor>) -> MonitorSetup {
+        let mut setup = MonitorSetup { monitors };
+        setup.process_monitors();
+
+        setup
+    }
+
     /// Reloads the list of monitors from the source.
+    /// Mirrored outputs, which occupy the exact same Rectangle, are grouped into a single Monitor.
+    /// The monitors are sorted in `TraversalOrder::ClockwiseFromOrigin` order.
     pub fn reload<E>(&mut self, loader: impl LoadMonitors<E>) -> Result<(), E> {
         self.monitors = loader.load_monitors()?;
+        self.process_monitors();
 
+        Ok(())
+    }
+
+    /// Groups mirrors, derives Xinerama indices and sorts freshly loaded monitors.
+    fn process_monitors(&mut self) {
+        self.group_mirrors();
+        self.derive_xinerama_indices();
         // now, sort them in clockwise order
         self.sort_clockwise();
-        Ok(())
+    }
+
+    /// Merges monitors with identical Rectangles into the first of them, recording the names of
+    /// the others as its mirrors. This way, traversals never yield the same region twice.
+    fn group_mirrors(&mut self) {
+        let mut grouped: Vec<Monitor> = Vec::with_capacity(self.monitors.len());
+
+        for monitor in self.monitors.drain(..) {
+            match grouped.iter_mut().find(|m| m.rect == monitor.rect) {
+                Some(group) => {
+                    group.primary |= monitor.primary;
+                    group.xinerama_index = match (group.xinerama_index, monitor.xinerama_index) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    };
+                    group.mirrors.extend(monitor.members().map(str::to_owned));
+                }
+                None => grouped.push(monitor),
+            }
+        }
+
+        self.monitors = grouped;
+    }
+
+    /// Assigns Xinerama indices to the monitors if the loader did not provide them for every
+    /// monitor, following the rules used by the X server to emulate Xinerama on top of RandR: the
+    /// primary monitor comes first, followed by the others in the order of their CRTCs.
+    fn derive_xinerama_indices(&mut self) {
+        if self.monitors.iter().all(|m| m.xinerama_index.is_some()) {
+            return;
+        }
+
+        let primary = self.monitors.iter().filter(|m| m.primary);
+        let mut others = self
+            .monitors
+            .iter()
+            .filter(|m| !m.primary)
+            .collect::<Vec<_>>();
+        others.sort_by_key(|m| m.crtc);
+        let names = primary
+            .chain(others)
+            .map(|m| m.name.clone())
+            .collect::<Vec<_>>();
+
+        for monitor in &mut self.monitors {
+            let index = names.iter().position(|name| *name == monitor.name);
+            monitor.xinerama_index = index.map(|index| index as u32);
+        }
+    }
+
+    /// Yields the monitor with the given Xinerama index.
+    pub fn monitor_by_xinerama_index(&self, xinerama_index: u32) -> LibResult<&Monitor> {
+        self.monitors
+            .iter()
+            .find(|monitor| monitor.xinerama_index == Some(xinerama_index))
+            .ok_or(MonitorUtilsError::UnknownXineramaIndex(xinerama_index))
+    }
+
+    /// Yields the monitor displayed by the adapter with the given name, including adapters which
+    /// mirror another monitor.
+    pub fn monitor_by_name(&self, name: &str) -> Option<&Monitor> {
+        self.monitors
+            .iter()
+            .find(|monitor| monitor.members().any(|member| member == name))
+    }
+
+    /// Yields every monitor in the setup, in traversal order.
+    pub fn monitors(&self) -> &[Monitor] {
+        &self.monitors
     }
 
     /// Sorts the internal list of monitors in a clockwise order, with further monitors coming
@@ -248,7 +932,8 @@ impl MonitorSetup {
 
                 // https://stackoverflow.com/questions/17530169/get-angle-between-point-and-origin
                 let angle = f32::atan2(top_left.y() as f32, top_left.x() as f32);
-                let distance = ((top_left.x().pow(2) + top_left.y().pow(2)) as f32).sqrt();
+                // squaring the coordinates as integers would overflow for offsets above 65535
+                let distance = (top_left.x() as f32).hypot(top_left.y() as f32);
 
                 (angle, distance)
             };
@@ -272,20 +957,124 @@ impl MonitorSetup {
 
     /// Yields the monitor which contains the given point.
     pub fn monitor_containing_point(&self, point: &Point) -> LibResult<&Monitor> {
+        self.monitor_containing_point_in(point, CoordinateSpace::Logical)
+    }
+
+    /// Yields the monitor which contains the given point in the given coordinate space.
+    pub fn monitor_containing_point_in(
+        &self,
+        point: &Point,
+        space: CoordinateSpace,
+    ) -> LibResult<&Monitor> {
         self.monitors
             .iter()
-            .filter(|m| m.rect.contains_point(&point))
-            .next()
+            .find(|m| self.rect_in(m, space).contains_point(point))
             .ok_or(MonitorUtilsError::PointOutOfBounds(*point))
     }
 
+    /// Yields the highest scale factor of any monitor, by which logical offsets are multiplied in
+    /// physical space.
+    fn max_scale(&self) -> Scale {
+        self.monitors
+            .iter()
+            .map(|m| m.scale())
+            .max_by_key(Scale::as_120ths)
+            .unwrap_or_default()
+    }
+
+    /// Yields the Rectangle covered by the given monitor in physical pixels.
+    pub fn physical_rect(&self, monitor: &Monitor) -> Rect {
+        let offset = self.max_scale().to_physical_point(&monitor.rect.offset);
+
+        Rect::new(
+            monitor.scale().to_physical(monitor.rect.width),
+            monitor.scale().to_physical(monitor.rect.height),
+            offset,
+        )
+    }
+
+    /// Yields the Rectangle covered by the given monitor in the given coordinate space.
+    pub fn rect_in(&self, monitor: &Monitor, space: CoordinateSpace) -> Rect {
+        match space {
+            CoordinateSpace::Logical => monitor.rect.clone(),
+            CoordinateSpace::Physical => self.physical_rect(monitor),
+        }
+    }
+
+    /// Converts a logical point to physical pixels, using the scale of the monitor containing it.
+    pub fn to_physical(&self, point: &Point) -> LibResult<Point> {
+        let monitor = self.monitor_containing_point(point)?;
+        let physical_offset = self.physical_rect(monitor).offset;
+        let relative = Point::new(
+            point.x() - monitor.rect.offset.x(),
+            point.y() - monitor.rect.offset.y(),
+        );
+
+        Ok(physical_offset + monitor.to_physical_relative(&relative))
+    }
+
+    /// Converts a physical point to logical coordinates, using the scale of the monitor containing
+    /// it.
+    pub fn to_logical(&self, point: &Point) -> LibResult<Point> {
+        let monitor = self.monitor_containing_point_in(point, CoordinateSpace::Physical)?;
+        let physical_offset = self.physical_rect(monitor).offset;
+        let relative = Point::new(
+            point.x() - physical_offset.x(),
+            point.y() - physical_offset.y(),
+        );
+
+        Ok(monitor.rect.offset + monitor.scale().to_logical_point(&relative))
+    }
+
+    /// Yields the overlap between the given Rectangle and each Monitor in the MonitorSetup, in
+    /// clockwise order.
+    pub fn overlaps_with_rect(&self, rect: &Rect) -> Vec<MonitorOverlap<'_>> {
+        let rect_area = rect.area();
+
+        self.monitors
+            .iter()
+            .map(|monitor| {
+                let area = monitor.rect.intersection(rect).area();
+                let fraction = if rect_area == 0 {
+                    0.0
+                } else {
+                    area as f64 / rect_area as f64
+                };
+
+                MonitorOverlap {
+                    monitor,
+                    area,
+                    fraction,
+                }
+            })
+            .collect()
+    }
+
+    /// Yields the monitor which has the largest intersection with the given Rectangle, along with
+    /// the overlap between the Rectangle and every Monitor in the MonitorSetup.
+    /// Ties are broken in favor of the Monitor which comes first in the clockwise ordering.
+    pub fn monitor_for_rect(&self, rect: &Rect) -> LibResult<(&Monitor, Vec<MonitorOverlap<'_>>)> {
+        let overlaps = self.overlaps_with_rect(rect);
+
+        let best = overlaps
+            .iter()
+            .filter(|overlap| overlap.area > 0)
+            // `max_by_key` yields the last maximum, so reverse to prefer the earliest monitor
+            .rev()
+            .max_by_key(|overlap| overlap.area)
+            .map(|overlap| overlap.monitor)
+            .ok_or_else(|| MonitorUtilsError::RectOutOfBounds(rect.clone()))?;
+
+        Ok((best, overlaps))
+    }
+
     /// Given a monitor index and an offset, returns the monitor at the offset index, such that
     /// overflows loop back to the beginning, and underflows loop back from the end.
     fn monitor_at_offset_index(&self, index: u32, offset: i32) -> LibResult<&Monitor> {
         let num_monitors = self.monitors.len() as u32;
 
         // get rid of any redundant loops
-        let new_offset = (offset.abs() as u32 % num_monitors) as i32 * offset.signum();
+        let new_offset = (offset.unsigned_abs() % num_monitors) as i32 * offset.signum();
         let mut new_index = index as i32 + new_offset;
 
         if new_index < 0 {
@@ -299,33 +1088,101 @@ impl MonitorSetup {
     }
 
     /// Yields the next monitor in a clock-wise traversal of the MonitorSetup.
+    /// If a different `TraversalOrder` was set, this yields the next monitor in that order.
     pub fn next_monitor_clockwise(&self, monitor: &Monitor) -> LibResult<&Monitor> {
         self.monitor_at_offset_index(monitor.order, 1)
     }
 
     /// Yields the next monitor in a counter-clockwise traversal of the MonitorSetup.
+    /// If a different `TraversalOrder` was set, this yields the previous monitor in that order.
     pub fn next_monitor_counterclockwise(&self, monitor: &Monitor) -> LibResult<&Monitor> {
         self.monitor_at_offset_index(monitor.order, -1)
     }
 
+    /// Maps a point on the `from` Monitor to the point at the same relative position on the `to`
+    /// Monitor.
+    pub fn map_point(&self, point: &Point, from: &Monitor, to: &Monitor) -> Point {
+        self.map_point_in(point, from, to, CoordinateSpace::Logical)
+    }
+
+    /// Maps a point on the `from` Monitor to the point at the same relative position on the `to`
+    /// Monitor, where both the point and the result are in the given coordinate space.
+    pub fn map_point_in(
+        &self,
+        point: &Point,
+        from: &Monitor,
+        to: &Monitor,
+        space: CoordinateSpace,
+    ) -> Point {
+        let (from, to) = (self.rect_in(from, space), self.rect_in(to, space));
+        let (x, _) = map_segment(
+            (point.x(), 0),
+            (from.offset.x(), from.width),
+            (to.offset.x(), to.width),
+            MapMode::PreserveRelative,
+        );
+        let (y, _) = map_segment(
+            (point.y(), 0),
+            (from.offset.y(), from.height),
+            (to.offset.y(), to.height),
+            MapMode::PreserveRelative,
+        );
+
+        Point::new(x, y)
+    }
+
+    /// Maps a Rectangle lying on the `from` Monitor onto the `to` Monitor, as described by the
+    /// given `MapMode`.
+    /// This can be used to move a window to another monitor while keeping it in the same place.
+    pub fn map_rect(&self, rect: &Rect, from: &Monitor, to: &Monitor, mode: MapMode) -> Rect {
+        let (x, width) = map_segment(
+            (rect.offset.x(), rect.width),
+            (from.rect.offset.x(), from.rect.width),
+            (to.rect.offset.x(), to.rect.width),
+            mode,
+        );
+        let (y, height) = map_segment(
+            (rect.offset.y(), rect.height),
+            (from.rect.offset.y(), from.rect.height),
+            (to.rect.offset.y(), to.rect.height),
+            mode,
+        );
+
+        Rect::new(width, height, Point::new(x, y))
+    }
+
+    /// Yields the neighbor sharing the longest edge with the given side of the monitor.
+    fn monitor_on_side(&self, monitor: &Monitor, side: Side) -> LibResult<&Monitor> {
+        self.adjacency().neighbor_on(monitor, side).ok_or_else(|| {
+            MonitorUtilsError::NoAdjacentMonitor {
+                name: monitor.name.clone(),
+                side,
+            }
+        })
+    }
+
     /// Yields the monitor above the given monitor.
-    pub fn monitor_above(&self, _monitor: &Monitor) -> LibResult<&Monitor> {
-        todo!()
+    /// If several monitors border its top edge, the one sharing the longest edge is chosen.
+    pub fn monitor_above(&self, monitor: &Monitor) -> LibResult<&Monitor> {
+        self.monitor_on_side(monitor, Side::Top)
     }
 
     /// Yields the monitor below the given monitor.
-    pub fn monitor_below(&self, _monitor: &Monitor) -> LibResult<&Monitor> {
-        todo!()
+    /// If several monitors border its bottom edge, the one sharing the longest edge is chosen.
+    pub fn monitor_below(&self, monitor: &Monitor) -> LibResult<&Monitor> {
+        self.monitor_on_side(monitor, Side::Bottom)
     }
 
     /// Yields the monitor to the left of the given monitor.
-    pub fn monitor_left_of(&self, _monitor: &Monitor) -> LibResult<&Monitor> {
-        todo!()
+    /// If several monitors border its left edge, the one sharing the longest edge is chosen.
+    pub fn monitor_left_of(&self, monitor: &Monitor) -> LibResult<&Monitor> {
+        self.monitor_on_side(monitor, Side::Left)
     }
 
     /// Yields the monitor to the right of the given monitor.
-    pub fn monitor_right_of(&self, _monitor: &Monitor) -> LibResult<&Monitor> {
-        todo!()
+    /// If several monitors border its right edge, the one sharing the longest edge is chosen.
+    pub fn monitor_right_of(&self, monitor: &Monitor) -> LibResult<&Monitor> {
+        self.monitor_on_side(monitor, Side::Right)
     }
 }
 
@@ -335,3 +1192,10 @@ impl MonitorSetup {
 pub trait LoadMonitors<E> {
     fn load_monitors(&self) -> Result<Vec<Monitor>, E>;
 }
+
+/// Allows passing a loader by reference, so that it can be reused or inspected afterwards.
+impl<E, L: LoadMonitors<E> + ?Sized> LoadMonitors<E> for &L {
+    fn load_monitors(&self) -> Result<Vec<Monitor>, E> {
+        (**self).load_monitors()
+    }
+}
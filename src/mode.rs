@@ -0,0 +1,115 @@
+#[cfg(feature = "serialize")]
+use miniserde::{Deserialize, Serialize};
+
+use std::fmt;
+
+/// A video mode supported by the display of a `Monitor`, as reported by the loader.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Mode {
+    /// The width of the mode in pixels, before rotation.
+    width: u32,
+    /// The height of the mode in pixels, before rotation.
+    height: u32,
+    /// The refresh rate in millihertz, e.g. 59940 for 59.94 Hz, or 0 if unknown.
+    refresh_rate: u32,
+    /// Whether the Monitor is currently running in this mode.
+    current: bool,
+    /// Whether the display reports this mode as its preferred, i.e. usually native, mode.
+    preferred: bool,
+    /// Whether the mode is interlaced.
+    interlaced: bool,
+}
+
+impl Mode {
+    /// Creates a new `Mode` with the given resolution and refresh rate in millihertz.
+    pub fn new(width: u32, height: u32, refresh_rate: u32) -> Mode {
+        Mode {
+            width,
+            height,
+            refresh_rate,
+            current: false,
+            preferred: false,
+            interlaced: false,
+        }
+    }
+
+    /// Marks the mode as the one the Monitor is currently running in, or not.
+    pub fn with_current(mut self, current: bool) -> Mode {
+        self.current = current;
+        self
+    }
+
+    /// Marks the mode as the preferred mode of the display, or not.
+    pub fn with_preferred(mut self, preferred: bool) -> Mode {
+        self.preferred = preferred;
+        self
+    }
+
+    /// Marks the mode as interlaced, or not.
+    pub fn with_interlaced(mut self, interlaced: bool) -> Mode {
+        self.interlaced = interlaced;
+        self
+    }
+
+    /// Yields the width of the mode in pixels, before rotation.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Yields the height of the mode in pixels, before rotation.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Yields the refresh rate in millihertz, e.g. 59940 for 59.94 Hz, or 0 if the loader does not
+    /// know it.
+    pub fn refresh_rate(&self) -> u32 {
+        self.refresh_rate
+    }
+
+    /// Returns `true` if the Monitor is currently running in this mode.
+    pub fn is_current(&self) -> bool {
+        self.current
+    }
+
+    /// Returns `true` if the display reports this mode as its preferred mode.
+    pub fn is_preferred(&self) -> bool {
+        self.preferred
+    }
+
+    /// Returns `true` if the mode is interlaced.
+    pub fn is_interlaced(&self) -> bool {
+        self.interlaced
+    }
+
+    /// Returns `true` if this mode has fewer pixels than `other` along either axis.
+    pub fn is_smaller_than(&self, other: &Mode) -> bool {
+        self.width < other.width || self.height < other.height
+    }
+}
+
+impl fmt::Display for Mode {
+    /// Formats the mode like `xrandr` names it, followed by the refresh rate, e.g.
+    /// `1920x1080 @ 59.94 Hz`; an unknown refresh rate is left out.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}x{}{}",
+            self.width,
+            self.height,
+            if self.interlaced { "i" } else { "" }
+        )?;
+
+        if self.refresh_rate != 0 {
+            write!(
+                f,
+                " @ {}.{:02} Hz",
+                self.refresh_rate / 1000,
+                self.refresh_rate % 1000 / 10
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use crate::MonitorIdentity;
+
+/// An output to be paired: the name of the adapter, along with the identity of the display if it
+/// is known.
+pub(crate) type OutputKey<'a> = (&'a str, Option<&'a MonitorIdentity>);
+
+/// Returns `true` if both outputs are on the same adapter, and their displays are not known to
+/// differ.
+fn same_port((name, identity): &OutputKey, (other_name, other_identity): &OutputKey) -> bool {
+    name == other_name
+        && match (identity, other_identity) {
+            (Some(identity), Some(other_identity)) => identity == other_identity,
+            _ => true,
+        }
+}
+
+/// Returns `true` if both outputs have the same display, on any adapter.
+fn same_display((_, identity): &OutputKey, (_, other_identity): &OutputKey) -> bool {
+    identity.is_some() && identity == other_identity
+}
+
+/// Pairs every output with a distinct candidate, yielding the index of the candidate paired with
+/// each output, or `None` if there is none left for it.
+/// Outputs on the same adapter are paired first, after which displays which moved to another
+/// adapter are paired by their identity; a different display on the same adapter is not paired.
+pub(crate) fn pair_outputs(outputs: &[OutputKey], candidates: &[OutputKey]) -> Vec<Option<usize>> {
+    let mut pairs: Vec<Option<usize>> = vec![None; outputs.len()];
+    let mut used = vec![false; candidates.len()];
+
+    for matches in [same_port, same_display] {
+        for (pair, output) in pairs.iter_mut().zip(outputs) {
+            if pair.is_some() {
+                continue;
+            }
+
+            let index =
+                (0..candidates.len()).find(|&i| !used[i] && matches(output, &candidates[i]));
+            if let Some(index) = index {
+                used[index] = true;
+                *pair = Some(index);
+            }
+        }
+    }
+
+    pairs
+}
@@ -0,0 +1,190 @@
+use crate::pairing::{pair_outputs, OutputKey};
+use crate::{ConnectedOutput, LibResult, MonitorSetup, MonitorUtilsError};
+
+use directories::ProjectDirs;
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// The extension of the files in which profiles are stored.
+const PROFILE_EXTENSION: &str = "json";
+
+/// A directory of named `MonitorSetup`s, which can be saved while docked at a desk and restored
+/// when the same outputs are connected again.
+/// Each profile is stored as a JSON file named after the profile.
+#[derive(Clone, Debug)]
+pub struct Profiles {
+    directory: PathBuf,
+}
+
+/// Yields every output used by the setup, including mirroring adapters. The identities of
+/// mirroring adapters are not recorded.
+fn setup_outputs(setup: &MonitorSetup) -> Vec<OutputKey<'_>> {
+    setup
+        .monitors
+        .iter()
+        .flat_map(|monitor| {
+            let mirrors = monitor.mirrors.iter().map(|mirror| (mirror.as_str(), None));
+            std::iter::once((monitor.name.as_str(), monitor.identity.as_ref())).chain(mirrors)
+        })
+        .collect()
+}
+
+/// Pairs every output used by a setup with a distinct connected output, yielding the index of the
+/// connected output paired with each setup output, or `None` if they cannot all be paired.
+/// Connected outputs left over were disabled when the setup was saved, e.g. the panel of a laptop
+/// with its lid closed, so they are not paired.
+fn pair_connected(outputs: &[OutputKey], connected: &[ConnectedOutput]) -> Option<Vec<usize>> {
+    let connected = connected
+        .iter()
+        .map(|output| (output.name(), output.identity()))
+        .collect::<Vec<_>>();
+
+    pair_outputs(outputs, &connected).into_iter().collect()
+}
+
+impl Profiles {
+    /// Yields the profiles stored in the user's data directory, e.g.
+    /// `~/.local/share/monitor-utils/profiles` on Linux.
+    pub fn new() -> Profiles {
+        let directory = ProjectDirs::from("com.github", "srithon", "monitor-utils")
+            .expect("Home directory must be valid")
+            .data_dir()
+            .join("profiles");
+
+        Profiles { directory }
+    }
+
+    /// Yields the profiles stored in the given directory.
+    pub fn in_directory(directory: impl Into<PathBuf>) -> Profiles {
+        Profiles {
+            directory: directory.into(),
+        }
+    }
+
+    /// Yields the directory in which the profiles are stored.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Yields the path of the file storing the given profile, if the name is valid.
+    fn profile_file(&self, name: &str) -> LibResult<PathBuf> {
+        // the name becomes a file name, so it must not escape the directory
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(MonitorUtilsError::InvalidProfileName(name.to_owned()));
+        }
+
+        Ok(self
+            .directory
+            .join(format!("{}.{}", name, PROFILE_EXTENSION)))
+    }
+
+    /// Stores the setup under the given name, replacing any existing profile of that name.
+    pub fn save(&self, name: &str, setup: &MonitorSetup) -> LibResult<()> {
+        let file = self.profile_file(name)?;
+
+        std::fs::create_dir_all(&self.directory)?;
+        std::fs::write(file, miniserde::json::to_string(setup))?;
+
+        Ok(())
+    }
+
+    /// Reads the setup stored under the given name.
+    pub fn load(&self, name: &str) -> LibResult<MonitorSetup> {
+        let file = self.profile_file(name)?;
+
+        match std::fs::read_to_string(file) {
+            Ok(string) => MonitorSetup::from_json(&string),
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                Err(MonitorUtilsError::UnknownProfile(name.to_owned()))
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Yields the names of every stored profile, in alphabetical order.
+    pub fn list(&self) -> LibResult<Vec<String>> {
+        let entries = match std::fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            // nothing has been saved yet
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut names = Vec::new();
+
+        for entry in entries {
+            let path = entry?.path();
+
+            if path.extension().is_some_and(|ext| ext == PROFILE_EXTENSION) {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(name.to_owned());
+                }
+            }
+        }
+
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Reads the setup stored under the given name, adapted to the given connected outputs: if
+    /// a display of the profile is now plugged into another port, as identified by its EDID, the
+    /// setup refers to the new port instead.
+    /// Connected outputs which the profile does not use are left out of the setup.
+    /// If the profile was not made for the connected outputs, the setup is returned unchanged.
+    pub fn load_for_outputs(
+        &self,
+        name: &str,
+        connected: &[ConnectedOutput],
+    ) -> LibResult<MonitorSetup> {
+        let mut setup = self.load(name)?;
+
+        let pairs = pair_connected(&setup_outputs(&setup), connected);
+        if let Some(pairs) = pairs {
+            let mut new_names = pairs.into_iter().map(|index| connected[index].name());
+
+            for monitor in &mut setup.monitors {
+                for member in std::iter::once(&mut monitor.name).chain(&mut monitor.mirrors) {
+                    *member = new_names
+                        .next()
+                        .expect("Every output must be paired")
+                        .to_owned();
+                }
+            }
+        }
+
+        Ok(setup)
+    }
+
+    /// Yields the name of the profile made for the given connected outputs, if any.
+    /// Every output used by the profile must be connected, while connected outputs which the
+    /// profile leaves off are ignored; if several profiles match, the one using the most outputs
+    /// wins, so that a docked profile is preferred over one for the laptop alone.
+    /// Displays are matched by their identity where it is known, so that a profile still applies
+    /// when a display is plugged into another port; otherwise, they are matched by the adapter
+    /// name. Profiles which cannot be read are skipped.
+    pub fn detect(&self, connected: &[ConnectedOutput]) -> LibResult<Option<String>> {
+        let mut best: Option<(String, usize)> = None;
+
+        for name in self.list()? {
+            if let Ok(setup) = self.load(&name) {
+                let outputs = setup_outputs(&setup);
+
+                let better = !matches!(best, Some((_, count)) if count >= outputs.len());
+
+                if better && pair_connected(&outputs, connected).is_some() {
+                    best = Some((name, outputs.len()));
+                }
+            }
+        }
+
+        Ok(best.map(|(name, _)| name))
+    }
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
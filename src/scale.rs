@@ -0,0 +1,103 @@
+#[cfg(feature = "serialize")]
+use miniserde::{Deserialize, Serialize};
+
+use crate::Point;
+
+use std::fmt;
+
+/// The factor by which a Monitor's logical coordinates are multiplied to yield physical pixels,
+/// e.g. 2 for a HiDPI display rendering everything at double size.
+/// The factor is stored in multiples of 1/120, like the Wayland fractional scale protocol, so that
+/// common factors such as 1.25 or 1.5 are exact.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Scale {
+    /// The scale factor in multiples of 1/120.
+    numerator: u32,
+}
+
+impl Scale {
+    /// The scale of a Monitor whose logical and physical coordinates are the same.
+    pub const ONE: Scale = Scale { numerator: 120 };
+
+    const DENOMINATOR: u64 = 120;
+
+    /// Creates a `Scale` from a factor in multiples of 1/120, e.g. 150 for 1.25.
+    /// Yields `None` for a factor of 0.
+    pub fn from_120ths(numerator: u32) -> Option<Scale> {
+        (numerator > 0).then_some(Scale { numerator })
+    }
+
+    /// Creates a `Scale` from the given factor, rounded to the nearest multiple of 1/120.
+    /// Yields `None` if the factor is not positive or too small to be represented.
+    pub fn from_f64(factor: f64) -> Option<Scale> {
+        let numerator = (factor * Self::DENOMINATOR as f64).round();
+
+        if numerator.is_finite() && numerator <= u32::MAX as f64 {
+            Scale::from_120ths(numerator as u32)
+        } else {
+            None
+        }
+    }
+
+    /// Yields the scale factor in multiples of 1/120.
+    pub fn as_120ths(&self) -> u32 {
+        self.numerator
+    }
+
+    /// Yields the scale factor.
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / Self::DENOMINATOR as f64
+    }
+
+    /// Converts a logical length or coordinate to physical pixels, rounding up so that converting
+    /// the result back with `to_logical` yields the original value for factors of at least 1.
+    pub(crate) fn to_physical(self, logical: u32) -> u32 {
+        let physical = (logical as u64 * self.numerator as u64).div_ceil(Self::DENOMINATOR);
+        physical.min(u32::MAX as u64) as u32
+    }
+
+    /// Converts a physical length or coordinate to the logical pixel covering it.
+    pub(crate) fn to_logical(self, physical: u32) -> u32 {
+        let logical = physical as u64 * Self::DENOMINATOR / self.numerator as u64;
+        logical.min(u32::MAX as u64) as u32
+    }
+
+    /// Converts a logical point to physical pixels.
+    pub(crate) fn to_physical_point(self, point: &Point) -> Point {
+        Point::new(self.to_physical(point.x()), self.to_physical(point.y()))
+    }
+
+    /// Converts a physical point to the logical pixel covering it.
+    pub(crate) fn to_logical_point(self, point: &Point) -> Point {
+        Point::new(self.to_logical(point.x()), self.to_logical(point.y()))
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Scale {
+        Scale::ONE
+    }
+}
+
+impl fmt::Display for Scale {
+    /// Formats the factor with up to three decimals, e.g. `1.25` or `2`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let formatted = format!("{:.3}", self.to_f64());
+        f.write_str(formatted.trim_end_matches('0').trim_end_matches('.'))
+    }
+}
+
+/// The coordinate space in which points and Rectangles are expressed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum CoordinateSpace {
+    /// The coordinates of the layout, in which windows are positioned and which `Monitor::rect`
+    /// uses. On X11, this is the framebuffer set up by `xrandr --pos` and `--scale`.
+    #[default]
+    Logical,
+    /// The pixels of the displays, in which e.g. screenshots are taken. Each Monitor lies at its
+    /// logical offset multiplied by the highest scale factor of the `MonitorSetup`, which keeps
+    /// Monitors from overlapping, and spans its logical size multiplied by its own scale factor.
+    /// Monitors with a lower scale factor than their neighbours are therefore followed by a gap.
+    Physical,
+}
@@ -0,0 +1,226 @@
+//! Support for testing code which depends on the monitor layout, without a display.
+
+use crate::{LoadMonitors, Monitor, MonitorSetup, Rect, Rotation};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// The error yielded by a `MockMonitorLoader` when a failure has been injected.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MockLoadError {
+    message: String,
+}
+
+impl MockLoadError {
+    /// Yields the message given when the failure was injected.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for MockLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mock loader failed: {}", self.message)
+    }
+}
+
+impl std::error::Error for MockLoadError {}
+
+/// This is an implementor for `LoadMonitors` which yields a fixed list of monitors, for testing
+/// code which depends on the monitor layout.
+/// Failures can be injected for every call or for specific calls, and the number of calls is
+/// recorded. Pass the loader by reference to keep access to it after creating a `MonitorSetup`:
+///
+/// ```
+/// # use monitor_utils::{testing::MockMonitorLoader, MonitorSetup};
+/// let loader = MockMonitorLoader::dual_side_by_side().fail_on_call(2, "unplugged");
+///
+/// let mut setup = MonitorSetup::with_loader(&loader).unwrap();
+/// assert!(setup.reload(&loader).is_err());
+/// assert_eq!(loader.call_count(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct MockMonitorLoader {
+    monitors: Mutex<Vec<Monitor>>,
+    /// If set, every call fails with this message.
+    failure: Option<String>,
+    /// Messages of the failures injected for specific calls, keyed by the (1-based) call number.
+    failures_on_call: BTreeMap<usize, String>,
+    calls: AtomicUsize,
+}
+
+impl MockMonitorLoader {
+    /// Creates a loader without any monitors.
+    pub fn new() -> MockMonitorLoader {
+        Self::default()
+    }
+
+    /// Creates a loader yielding the given monitors.
+    pub fn with_monitors(monitors: Vec<Monitor>) -> MockMonitorLoader {
+        MockMonitorLoader {
+            monitors: Mutex::new(monitors),
+            ..Self::default()
+        }
+    }
+
+    /// A single 1920x1080 monitor, `eDP-1`.
+    pub fn single_1080p() -> MockMonitorLoader {
+        Self::new()
+            .with_output("eDP-1", "1920x1080+0+0")
+            .primary("eDP-1")
+    }
+
+    /// Two 1920x1080 monitors side by side: `DP-1` on the left and `HDMI-1` on the right.
+    pub fn dual_side_by_side() -> MockMonitorLoader {
+        Self::new()
+            .with_output("DP-1", "1920x1080+0+0")
+            .with_output("HDMI-1", "1920x1080+1920+0")
+            .primary("DP-1")
+    }
+
+    /// Three 1920x1080 monitors side by side: `DP-1`, `DP-2` and `DP-3` from left to right.
+    pub fn triple_side_by_side() -> MockMonitorLoader {
+        Self::new()
+            .with_output("DP-1", "1920x1080+0+0")
+            .with_output("DP-2", "1920x1080+1920+0")
+            .with_output("DP-3", "1920x1080+3840+0")
+            .primary("DP-2")
+    }
+
+    /// A 1920x1080 laptop panel, `eDP-1`, with a 1080p monitor rotated into portrait mode to its
+    /// right, `HDMI-1`. The bottom edges of the two monitors are aligned.
+    pub fn laptop_plus_vertical() -> MockMonitorLoader {
+        let vertical = Monitor::new("HDMI-1".to_owned(), 1, parse_geometry("1080x1920+1920+0"))
+            .with_rotation(Rotation::Left);
+
+        Self::new()
+            .with_output("eDP-1", "1920x1080+0+840")
+            .with_monitor(vertical)
+            .primary("eDP-1")
+    }
+
+    /// A 2560x1440 monitor, `DP-1`, above a centered 1920x1080 laptop panel, `eDP-1`.
+    pub fn laptop_below_external() -> MockMonitorLoader {
+        Self::new()
+            .with_output("DP-1", "2560x1440+0+0")
+            .with_output("eDP-1", "1920x1080+320+1440")
+            .primary("DP-1")
+    }
+
+    /// A 1920x1080 laptop panel, `eDP-1`, mirrored onto a projector, `HDMI-1`.
+    pub fn mirrored_projector() -> MockMonitorLoader {
+        Self::new()
+            .with_output("eDP-1", "1920x1080+0+0")
+            .with_output("HDMI-1", "1920x1080+0+0")
+            .primary("eDP-1")
+    }
+
+    /// Adds the given monitor.
+    pub fn with_monitor(self, monitor: Monitor) -> MockMonitorLoader {
+        self.monitors
+            .lock()
+            .expect("Mock loader must not be poisoned")
+            .push(monitor);
+        self
+    }
+
+    /// Adds a monitor with the given name and geometry of the form `WxH+X+Y`, whose CRTC is its
+    /// index among the monitors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the geometry cannot be parsed.
+    pub fn with_output(self, name: &str, geometry: &str) -> MockMonitorLoader {
+        let crtc = self.monitors().len() as u32;
+        self.with_monitor(Monitor::new(
+            name.to_owned(),
+            crtc,
+            parse_geometry(geometry),
+        ))
+    }
+
+    /// Marks the monitor with the given name as the primary monitor, if it exists.
+    pub fn primary(self, name: &str) -> MockMonitorLoader {
+        for monitor in self
+            .monitors
+            .lock()
+            .expect("Mock loader must not be poisoned")
+            .iter_mut()
+        {
+            monitor.primary = monitor.name == name;
+        }
+
+        self
+    }
+
+    /// Makes every call fail with the given message.
+    pub fn fail_always(mut self, message: &str) -> MockMonitorLoader {
+        self.failure = Some(message.to_owned());
+        self
+    }
+
+    /// Makes the given call fail with the given message, where the first call is 1.
+    pub fn fail_on_call(mut self, call: usize, message: &str) -> MockMonitorLoader {
+        self.failures_on_call.insert(call, message.to_owned());
+        self
+    }
+
+    /// Replaces the monitors yielded by subsequent calls, e.g. to simulate plugging in a monitor
+    /// before calling `MonitorSetup::reload`.
+    pub fn set_monitors(&self, monitors: Vec<Monitor>) {
+        *self
+            .monitors
+            .lock()
+            .expect("Mock loader must not be poisoned") = monitors;
+    }
+
+    /// Yields a copy of the monitors yielded by the loader.
+    pub fn monitors(&self) -> Vec<Monitor> {
+        self.monitors
+            .lock()
+            .expect("Mock loader must not be poisoned")
+            .clone()
+    }
+
+    /// Yields the number of times `load_monitors` has been called, including failed calls.
+    pub fn call_count(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Creates a `MonitorSetup` from the loader.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a failure has been injected for the call.
+    pub fn to_setup(&self) -> MonitorSetup {
+        MonitorSetup::with_loader(self).expect("Mock loader must not fail")
+    }
+}
+
+/// Parses a geometry given to the mock loader, which is expected to be valid.
+fn parse_geometry(geometry: &str) -> Rect {
+    geometry
+        .parse()
+        .unwrap_or_else(|error| panic!("Mock geometry must be valid: {}", error))
+}
+
+impl LoadMonitors<MockLoadError> for MockMonitorLoader {
+    /// Yields the monitors, unless a failure has been injected for this call.
+    fn load_monitors(&self) -> Result<Vec<Monitor>, MockLoadError> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let failure = self
+            .failure
+            .as_ref()
+            .or_else(|| self.failures_on_call.get(&call));
+
+        match failure {
+            Some(message) => Err(MockLoadError {
+                message: message.clone(),
+            }),
+            None => Ok(self.monitors()),
+        }
+    }
+}
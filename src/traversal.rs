@@ -0,0 +1,145 @@
+use crate::{Monitor, MonitorSetup};
+
+use std::cmp::Ordering;
+use std::f32::consts::PI;
+
+/// The order in which the Monitors of a `MonitorSetup` are traversed by
+/// `next_monitor_clockwise` and `next_monitor_counterclockwise`.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub enum TraversalOrder {
+    /// Clockwise by the angle of each Monitor's top-left corner around the top-left corner of the
+    /// virtual screen, with closer Monitors coming first to break ties.
+    #[default]
+    ClockwiseFromOrigin,
+    /// Clockwise by the angle of each Monitor's center around the average of all centers,
+    /// starting from the top-left.
+    ClockwiseAroundCentroid,
+    /// Rows from top to bottom, with Monitors ordered from left to right within each row.
+    /// Monitors belong to the same row if their vertical centers lie within the first Monitor of
+    /// the row.
+    RowMajor,
+    /// Columns from left to right, with Monitors ordered from top to bottom within each column.
+    ColumnMajor,
+    /// Alphabetically by adapter name.
+    ByName,
+    /// By Xinerama index, with any Monitors lacking an index coming last.
+    ByXineramaIndex,
+    /// The Monitors named in the list come first, in the given order, followed by any others in
+    /// `ClockwiseFromOrigin` order. Mirroring adapters may be named in place of their Monitor.
+    Explicit(Vec<String>),
+}
+
+/// Compares two floats which are known not to be NaN.
+fn compare_floats(a: f32, b: f32) -> Ordering {
+    a.partial_cmp(&b).expect("Should not have NaN values.")
+}
+
+/// Yields the index of the line (row or column) that each Monitor belongs to, where `span` yields
+/// the start and length of a Monitor along the axis perpendicular to the lines.
+fn line_indices(monitors: &[Monitor], span: impl Fn(&Monitor) -> (u32, u32)) -> Vec<usize> {
+    let mut by_start = (0..monitors.len()).collect::<Vec<_>>();
+    by_start.sort_by_key(|&index| span(&monitors[index]));
+
+    let mut lines = vec![0; monitors.len()];
+    let mut line = 0;
+    // the span of the first Monitor in the current line
+    let mut line_span: Option<(u32, u32)> = None;
+
+    for index in by_start {
+        let (start, length) = span(&monitors[index]);
+        // saturate, so that Monitors reaching past the end of the coordinate space are clipped
+        let middle = start.saturating_add(length / 2);
+
+        match line_span {
+            Some((line_start, line_length))
+                if middle >= line_start && middle < line_start.saturating_add(line_length) => {}
+            Some(_) => {
+                line += 1;
+                line_span = Some((start, length));
+            }
+            None => line_span = Some((start, length)),
+        }
+
+        lines[index] = line;
+    }
+
+    lines
+}
+
+impl MonitorSetup {
+    /// Sorts the monitors in the given traversal order, which determines the results of
+    /// `next_monitor_clockwise` and `next_monitor_counterclockwise`.
+    /// Note that `reload` resets the order to `TraversalOrder::ClockwiseFromOrigin`.
+    pub fn set_traversal_order(&mut self, order: &TraversalOrder) {
+        match order {
+            TraversalOrder::ClockwiseFromOrigin => self.sort_clockwise(),
+            TraversalOrder::ClockwiseAroundCentroid => {
+                let count = self.monitors.len().max(1) as f32;
+                let (sum_x, sum_y) = self.monitors.iter().fold((0.0, 0.0), |(x, y), m| {
+                    let center = m.rect.center();
+                    (x + center.x() as f32, y + center.y() as f32)
+                });
+                let centroid = (sum_x / count, sum_y / count);
+
+                let to_angle_distance = |monitor: &Monitor| {
+                    let center = monitor.rect.center();
+                    let dx = center.x() as f32 - centroid.0;
+                    let dy = center.y() as f32 - centroid.1;
+
+                    // the y axis points down, so increasing angles are clockwise; rotate such
+                    // that the top-left direction comes first
+                    let angle = (f32::atan2(dy, dx) + 3.0 * PI / 4.0).rem_euclid(2.0 * PI);
+                    (angle, dx.hypot(dy))
+                };
+
+                self.monitors.sort_by(|m1, m2| {
+                    let (angle1, distance1) = to_angle_distance(m1);
+                    let (angle2, distance2) = to_angle_distance(m2);
+
+                    compare_floats(angle1, angle2).then(compare_floats(distance1, distance2))
+                });
+            }
+            TraversalOrder::RowMajor | TraversalOrder::ColumnMajor => {
+                let row_major = *order == TraversalOrder::RowMajor;
+
+                let lines = line_indices(&self.monitors, |m| {
+                    if row_major {
+                        (m.rect.offset.y(), m.rect.height)
+                    } else {
+                        (m.rect.offset.x(), m.rect.width)
+                    }
+                });
+
+                let mut keyed = self.monitors.drain(..).zip(lines).collect::<Vec<_>>();
+                keyed.sort_by_key(|(m, line)| {
+                    let offset = m.rect.offset;
+                    if row_major {
+                        (*line, offset.x(), offset.y())
+                    } else {
+                        (*line, offset.y(), offset.x())
+                    }
+                });
+
+                self.monitors = keyed.into_iter().map(|(m, _)| m).collect();
+            }
+            TraversalOrder::ByName => self.monitors.sort_by(|m1, m2| m1.name.cmp(&m2.name)),
+            TraversalOrder::ByXineramaIndex => self
+                .monitors
+                .sort_by_key(|m| (m.xinerama_index.is_none(), m.xinerama_index)),
+            TraversalOrder::Explicit(names) => {
+                self.sort_clockwise();
+
+                // the sort is stable, so unlisted monitors keep their clockwise order
+                let position = |monitor: &Monitor| {
+                    names
+                        .iter()
+                        .position(|name| monitor.members().any(|member| member == name))
+                        .unwrap_or(names.len())
+                };
+                self.monitors.sort_by_key(position);
+            }
+        }
+
+        self.update_monitor_ordering();
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{Mode, MonitorSetup, Rect};
+
+use std::fmt;
+
+/// A problem found in the layout of a `MonitorSetup`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LayoutIssue {
+    /// The Monitor has zero width or zero height.
+    ZeroSize { monitor: String },
+    /// The two Monitors partially overlap.
+    Overlap {
+        first: String,
+        second: String,
+        area: u64,
+    },
+    /// The two Monitors occupy the exact same Rectangle, which usually means that one output is
+    /// cloning the other.
+    Mirrored { first: String, second: String },
+    /// The two Monitors are the closest Monitors between two disconnected islands, and are
+    /// separated by the given horizontal and vertical distance.
+    Gap {
+        first: String,
+        second: String,
+        horizontal: u32,
+        vertical: u32,
+    },
+    /// The Monitors form several groups which do not touch each other, so the cursor cannot move
+    /// between them.
+    DisconnectedIslands { islands: Vec<Vec<String>> },
+    /// The Monitor is running in a smaller mode than the preferred mode of its display, which is
+    /// usually its native resolution, so the image is scaled and blurry.
+    BelowNativeResolution {
+        monitor: String,
+        current: Mode,
+        native: Mode,
+    },
+}
+
+impl LayoutIssue {
+    /// Returns `true` if the issue leaves the layout in a broken state, or `false` if it is merely
+    /// worth pointing out.
+    pub fn is_error(&self) -> bool {
+        !matches!(
+            self,
+            LayoutIssue::Mirrored { .. } | LayoutIssue::BelowNativeResolution { .. }
+        )
+    }
+}
+
+impl fmt::Display for LayoutIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutIssue::ZeroSize { monitor } => write!(f, "{} has zero size", monitor),
+            LayoutIssue::Overlap {
+                first,
+                second,
+                area,
+            } => write!(f, "{} overlaps {} by {} pixels", first, second, area),
+            LayoutIssue::Mirrored { first, second } => {
+                write!(f, "{} mirrors {}", first, second)
+            }
+            LayoutIssue::Gap {
+                first,
+                second,
+                horizontal,
+                vertical,
+            } => write!(
+                f,
+                "gap of {}x{} pixels between {} and {}",
+                horizontal, vertical, first, second
+            ),
+            LayoutIssue::DisconnectedIslands { islands } => {
+                let islands = islands
+                    .iter()
+                    .map(|island| format!("[{}]", island.join(", ")))
+                    .collect::<Vec<_>>();
+
+                write!(
+                    f,
+                    "monitors form disconnected islands: {}",
+                    islands.join(" ")
+                )
+            }
+            LayoutIssue::BelowNativeResolution {
+                monitor,
+                current,
+                native,
+            } => write!(
+                f,
+                "{} runs at {}x{}, below its native resolution of {}x{}",
+                monitor,
+                current.width(),
+                current.height(),
+                native.width(),
+                native.height()
+            ),
+        }
+    }
+}
+
+/// The result of validating a `MonitorSetup`, listing every issue found in its layout.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ValidationReport {
+    issues: Vec<LayoutIssue>,
+}
+
+impl ValidationReport {
+    /// Yields every issue found in the layout.
+    pub fn issues(&self) -> &[LayoutIssue] {
+        &self.issues
+    }
+
+    /// Returns `true` if any of the issues is an error.
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(LayoutIssue::is_error)
+    }
+}
+
+/// Yields the horizontal and vertical distance between two Rectangles, which is zero along an axis
+/// on which they overlap or touch.
+fn distance(a: &Rect, b: &Rect) -> (u32, u32) {
+    let axis_distance = |a_start: u32, a_length: u32, b_start: u32, b_length: u32| {
+        // saturate, so that Rectangles reaching past the end of the coordinate space are clipped
+        let (a_end, b_end) = (
+            a_start.saturating_add(a_length),
+            b_start.saturating_add(b_length),
+        );
+
+        b_start
+            .saturating_sub(a_end)
+            .max(a_start.saturating_sub(b_end))
+    };
+
+    (
+        axis_distance(a.offset.x(), a.width, b.offset.x(), b.width),
+        axis_distance(a.offset.y(), a.height, b.offset.y(), b.height),
+    )
+}
+
+/// Yields the representative of the set containing `index` in the union-find forest.
+fn find(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    parents[index] = root;
+    root
+}
+
+/// Merges the sets containing `a` and `b` in the union-find forest.
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let (root_a, root_b) = (find(parents, a), find(parents, b));
+    parents[root_b] = root_a;
+}
+
+impl MonitorSetup {
+    /// Checks the layout for zero-size, overlapping and mirrored monitors, monitors which cannot
+    /// be reached from the others, and monitors running below their native resolution, yielding a
+    /// report of every issue found.
+    pub fn validate(&self) -> ValidationReport {
+        let mut issues = Vec::new();
+        let monitors = &self.monitors;
+
+        for monitor in monitors.iter().filter(|m| m.rect.is_empty()) {
+            issues.push(LayoutIssue::ZeroSize {
+                monitor: monitor.name.clone(),
+            });
+        }
+
+        for monitor in monitors {
+            for mirror in &monitor.mirrors {
+                issues.push(LayoutIssue::Mirrored {
+                    first: monitor.name.clone(),
+                    second: mirror.clone(),
+                });
+            }
+        }
+
+        // only loaders which report modes allow this check
+        for monitor in monitors {
+            if let (Some(current), Some(native)) =
+                (monitor.current_mode(), monitor.preferred_mode())
+            {
+                if current.is_smaller_than(native) {
+                    issues.push(LayoutIssue::BelowNativeResolution {
+                        monitor: monitor.name.clone(),
+                        current: *current,
+                        native: *native,
+                    });
+                }
+            }
+        }
+
+        // monitors are connected if they share an edge or overlap
+        let mut parents = (0..monitors.len()).collect::<Vec<_>>();
+        let adjacency = self.adjacency();
+
+        for (i, first) in monitors.iter().enumerate() {
+            for (j, second) in monitors.iter().enumerate().skip(i + 1) {
+                if first.rect.is_empty() || second.rect.is_empty() {
+                    continue;
+                }
+
+                let intersection = first.rect.intersection(&second.rect);
+
+                // mirrors are grouped into a single Monitor, so identical Rectangles never meet here
+                if !intersection.is_empty() {
+                    issues.push(LayoutIssue::Overlap {
+                        first: first.name.clone(),
+                        second: second.name.clone(),
+                        area: intersection.area(),
+                    });
+                }
+
+                if !intersection.is_empty() || adjacency.are_adjacent(first, second) {
+                    union(&mut parents, i, j);
+                }
+            }
+        }
+
+        // group the non-empty monitors by island, keeping the clockwise order
+        let mut islands: Vec<(usize, Vec<usize>)> = Vec::new();
+        for index in (0..monitors.len()).filter(|&i| !monitors[i].rect.is_empty()) {
+            let root = find(&mut parents, index);
+            match islands
+                .iter_mut()
+                .find(|(island_root, _)| *island_root == root)
+            {
+                Some((_, members)) => members.push(index),
+                None => islands.push((root, vec![index])),
+            }
+        }
+
+        if islands.len() > 1 {
+            // report the closest pair of monitors between each pair of islands
+            for (i, (_, first_island)) in islands.iter().enumerate() {
+                for (_, second_island) in islands.iter().skip(i + 1) {
+                    let closest = first_island
+                        .iter()
+                        .flat_map(|&a| second_island.iter().map(move |&b| (a, b)))
+                        .map(|(a, b)| (a, b, distance(&monitors[a].rect, &monitors[b].rect)))
+                        .min_by_key(|&(_, _, (horizontal, vertical))| {
+                            horizontal as u64 * horizontal as u64
+                                + vertical as u64 * vertical as u64
+                        });
+
+                    if let Some((a, b, (horizontal, vertical))) = closest {
+                        issues.push(LayoutIssue::Gap {
+                            first: monitors[a].name.clone(),
+                            second: monitors[b].name.clone(),
+                            horizontal,
+                            vertical,
+                        });
+                    }
+                }
+            }
+
+            issues.push(LayoutIssue::DisconnectedIslands {
+                islands: islands
+                    .iter()
+                    .map(|(_, members)| {
+                        members
+                            .iter()
+                            .map(|&index| monitors[index].name.clone())
+                            .collect()
+                    })
+                    .collect(),
+            });
+        }
+
+        ValidationReport { issues }
+    }
+}
@@ -0,0 +1,74 @@
+//! Loaders and event listeners for Wayland compositors, which do not report their outputs through
+//! RandR: sway, through its IPC socket, and Hyprland, through its request and event sockets.
+
+mod hyprland;
+pub use hyprland::{parse_hyprland_monitors, HyprlandEvents, HyprlandMonitorLoader};
+
+mod sway;
+pub use sway::{parse_sway_outputs, SwayEvents, SwayMonitorLoader};
+
+use crate::{Monitor, Point, Reflection, Rotation};
+
+use std::io::{Error, ErrorKind};
+
+/// A Wayland compositor which monitor-utils can query.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Compositor {
+    Sway,
+    Hyprland,
+}
+
+impl Compositor {
+    /// Yields the compositor running the current session, as announced by the environment
+    /// variables it sets for its clients (`SWAYSOCK` or `HYPRLAND_INSTANCE_SIGNATURE`), if any.
+    pub fn detect() -> Option<Compositor> {
+        if std::env::var_os("SWAYSOCK").is_some() {
+            Some(Compositor::Sway)
+        } else if std::env::var_os("HYPRLAND_INSTANCE_SIGNATURE").is_some() {
+            Some(Compositor::Hyprland)
+        } else {
+            None
+        }
+    }
+}
+
+/// Converts a `wl_output` transform, given as its rotation in degrees and whether it is flipped,
+/// into a rotation and reflection. Compositors rotate the image clockwise, so a transform of 90
+/// degrees is `Rotation::Right`; flipped transforms mirror the image horizontally.
+fn transform(degrees: u32, flipped: bool) -> Result<(Rotation, Reflection), Error> {
+    let rotation = match degrees {
+        0 => Rotation::Normal,
+        90 => Rotation::Right,
+        180 => Rotation::Inverted,
+        270 => Rotation::Left,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid transform: {} degrees", degrees),
+            ))
+        }
+    };
+    let reflection = if flipped {
+        Reflection::X
+    } else {
+        Reflection::Normal
+    };
+
+    Ok((rotation, reflection))
+}
+
+/// Wayland compositors allow outputs at negative positions, which a `Point` cannot represent, so
+/// the layout is shifted such that its top-left corner lies at the origin.
+fn shift_to_origin(outputs: Vec<(Monitor, (i64, i64))>) -> Vec<Monitor> {
+    let min_x = outputs.iter().map(|(_, (x, _))| *x).min().unwrap_or(0);
+    let min_y = outputs.iter().map(|(_, (_, y))| *y).min().unwrap_or(0);
+
+    outputs
+        .into_iter()
+        .map(|(mut monitor, (x, y))| {
+            let clamp = |value: i64| value.clamp(0, u32::MAX as i64) as u32;
+            monitor.rect.offset = Point::new(clamp(x - min_x), clamp(y - min_y));
+            monitor
+        })
+        .collect()
+}
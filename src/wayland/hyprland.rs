@@ -0,0 +1,238 @@
+use super::{shift_to_origin, transform};
+use crate::{LoadMonitors, Mode, Monitor, Point, Rect, Scale};
+
+use miniserde::Deserialize;
+
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Write};
+use std::net::Shutdown;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+
+/// The events on `.socket2.sock` which are sent when a monitor is plugged in or unplugged; the
+/// `v2` variants carry the monitor ID along with its name.
+const LAYOUT_EVENTS: [&str; 4] = [
+    "monitoradded",
+    "monitorremoved",
+    "monitoraddedv2",
+    "monitorremovedv2",
+];
+
+/// A monitor as reported by `hyprctl monitors -j`.
+#[derive(Deserialize)]
+struct HyprlandMonitor {
+    name: String,
+    /// The width of the current mode in physical pixels, before the transform.
+    width: u32,
+    /// The height of the current mode in physical pixels, before the transform.
+    height: u32,
+    #[serde(rename = "refreshRate")]
+    refresh_rate: f64,
+    x: i64,
+    y: i64,
+    scale: f64,
+    /// A `wl_output` transform: 0 to 3 rotate by multiples of 90 degrees, 4 to 7 are flipped.
+    transform: u32,
+    disabled: Option<bool>,
+    /// The modes supported by the display, e.g. `2560x1440@59.95Hz`.
+    #[serde(rename = "availableModes")]
+    available_modes: Option<Vec<String>>,
+}
+
+/// Yields the directory holding the sockets of the running Hyprland instance, which Hyprland
+/// announces in `HYPRLAND_INSTANCE_SIGNATURE`. Hyprland 0.40 moved it from `/tmp/hypr` to
+/// `$XDG_RUNTIME_DIR/hypr`.
+fn instance_directory() -> Result<PathBuf, Error> {
+    let signature = std::env::var_os("HYPRLAND_INSTANCE_SIGNATURE").ok_or_else(|| {
+        Error::new(
+            ErrorKind::NotFound,
+            "HYPRLAND_INSTANCE_SIGNATURE is not set",
+        )
+    })?;
+
+    let mut candidates = Vec::new();
+    if let Some(runtime_directory) = std::env::var_os("XDG_RUNTIME_DIR") {
+        candidates.push(PathBuf::from(runtime_directory).join("hypr"));
+    }
+    candidates.push(PathBuf::from("/tmp/hypr"));
+
+    candidates
+        .into_iter()
+        .map(|directory| directory.join(&signature))
+        .find(|directory| directory.is_dir())
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                "the directory of the Hyprland instance does not exist",
+            )
+        })
+}
+
+/// Parses a mode as listed in `availableModes`, e.g. `2560x1440@59.95Hz`.
+fn parse_mode(mode: &str) -> Option<Mode> {
+    let (size, refresh_rate) = mode.split_once('@')?;
+    let (width, height) = size.split_once('x')?;
+    let hertz = refresh_rate.strip_suffix("Hz")?.parse::<f64>().ok()?;
+
+    Some(Mode::new(
+        width.parse().ok()?,
+        height.parse().ok()?,
+        (hertz * 1000.0).round() as u32,
+    ))
+}
+
+/// Parses the monitors reported by `hyprctl monitors -j`, and returns the enabled ones, in the
+/// order they are listed.
+pub fn parse_hyprland_monitors(json: &str) -> Result<Vec<Monitor>, Error> {
+    let outputs: Vec<HyprlandMonitor> = miniserde::json::from_str(json).map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidData,
+            "invalid Hyprland monitors: expected a list of monitors with a name, size and position",
+        )
+    })?;
+
+    let mut monitors = Vec::new();
+
+    for output in outputs
+        .into_iter()
+        .filter(|output| !output.disabled.unwrap_or(false))
+    {
+        let (rotation, reflection) = transform(output.transform % 4 * 90, output.transform >= 4)
+            .ok()
+            .filter(|_| output.transform < 8)
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid transform of {}: {}", output.name, output.transform),
+                )
+            })?;
+
+        let scale = Scale::from_f64(output.scale).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid scale of {}: {}", output.name, output.scale),
+            )
+        })?;
+
+        // the refresh rates of the available modes are rounded to two decimals
+        let refresh_rate = (output.refresh_rate * 1000.0).round() as u32;
+        let modes = output
+            .available_modes
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|mode| parse_mode(mode))
+            .map(|mode| {
+                let current = mode.width() == output.width
+                    && mode.height() == output.height
+                    && mode.refresh_rate().abs_diff(refresh_rate) <= 5;
+                mode.with_current(current)
+            })
+            .collect();
+
+        // the layout is in logical coordinates, after the transform
+        let (width, height) = if rotation.is_sideways() {
+            (output.height, output.width)
+        } else {
+            (output.width, output.height)
+        };
+        let logical = |length: u32| (length as f64 / output.scale).round() as u32;
+        let rect = Rect::new(logical(width), logical(height), Point::new(0, 0));
+
+        let monitor = Monitor::new(output.name, monitors.len() as u32, rect)
+            .with_rotation(rotation)
+            .with_reflection(reflection)
+            .with_scale(scale)
+            .with_modes(modes);
+
+        monitors.push((monitor, (output.x, output.y)));
+    }
+
+    Ok(shift_to_origin(monitors))
+}
+
+/// This is an implementor for `LoadMonitors` which queries the monitors of Hyprland through its
+/// request socket, `.socket.sock`, like `hyprctl monitors -j`.
+/// Hyprland does not report the EDID of its monitors, so the monitors have no identity.
+pub struct HyprlandMonitorLoader {
+    socket: PathBuf,
+}
+
+impl HyprlandMonitorLoader {
+    /// Creates an instance of `HyprlandMonitorLoader` which connects to the request socket of the
+    /// instance named by the `HYPRLAND_INSTANCE_SIGNATURE` environment variable; yields an Error
+    /// if it is not set, or the instance does not exist.
+    pub fn new() -> Result<HyprlandMonitorLoader, Error> {
+        Ok(Self::with_socket(
+            instance_directory()?.join(".socket.sock"),
+        ))
+    }
+
+    /// Creates an instance of `HyprlandMonitorLoader` which connects to the given request socket.
+    pub fn with_socket(socket: impl Into<PathBuf>) -> HyprlandMonitorLoader {
+        HyprlandMonitorLoader {
+            socket: socket.into(),
+        }
+    }
+
+    /// Yields the path of the request socket.
+    pub fn socket(&self) -> &Path {
+        &self.socket
+    }
+}
+
+impl LoadMonitors<Error> for HyprlandMonitorLoader {
+    /// Queries the monitors of Hyprland and returns the enabled ones, laid out as in Hyprland.
+    fn load_monitors(&self) -> Result<Vec<Monitor>, Error> {
+        let mut stream = UnixStream::connect(&self.socket)?;
+        stream.write_all(b"j/monitors")?;
+        stream.shutdown(Shutdown::Write)?;
+
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply)?;
+
+        parse_hyprland_monitors(&reply)
+    }
+}
+
+/// Listens on the event socket of Hyprland, `.socket2.sock`, for monitors being plugged in or
+/// unplugged.
+pub struct HyprlandEvents {
+    reader: BufReader<UnixStream>,
+}
+
+impl HyprlandEvents {
+    /// Connects to the event socket of the instance named by the `HYPRLAND_INSTANCE_SIGNATURE`
+    /// environment variable.
+    pub fn new() -> Result<HyprlandEvents, Error> {
+        Self::with_socket(instance_directory()?.join(".socket2.sock"))
+    }
+
+    /// Connects to the given event socket.
+    pub fn with_socket(socket: impl AsRef<Path>) -> Result<HyprlandEvents, Error> {
+        Ok(HyprlandEvents {
+            reader: BufReader::new(UnixStream::connect(socket)?),
+        })
+    }
+
+    /// Blocks until Hyprland reports a monitor being added or removed, skipping every other
+    /// event, such as workspace changes. Each event is a line of the form `EVENT>>DATA`.
+    pub fn wait_for_change(&mut self) -> Result<(), Error> {
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "Hyprland closed the event socket",
+                ));
+            }
+
+            let event = line
+                .split_once(">>")
+                .map_or(line.trim_end(), |(event, _)| event);
+            if LAYOUT_EVENTS.contains(&event) {
+                return Ok(());
+            }
+        }
+    }
+}
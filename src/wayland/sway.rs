@@ -0,0 +1,245 @@
+use super::{shift_to_origin, transform};
+use crate::{LoadMonitors, Mode, Monitor, Point, Rect, Scale};
+
+use miniserde::Deserialize;
+
+use std::io::{Error, ErrorKind, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+
+/// The magic string starting every message on the sway IPC socket, which sway shares with i3.
+const IPC_MAGIC: &[u8; 6] = b"i3-ipc";
+
+const IPC_SUBSCRIBE: u32 = 2;
+const IPC_GET_OUTPUTS: u32 = 3;
+/// Events have the highest bit of their type set; the `output` event is the second event type.
+const IPC_OUTPUT_EVENT: u32 = 0x8000_0001;
+
+/// An output as reported by `swaymsg -t get_outputs`.
+#[derive(Deserialize)]
+struct SwayOutput {
+    name: String,
+    /// Whether the output is enabled; disabled outputs have an empty rectangle.
+    active: bool,
+    primary: Option<bool>,
+    scale: Option<f64>,
+    /// One of `normal`, `90`, `180`, `270`, `flipped`, `flipped-90`, `flipped-180` or
+    /// `flipped-270`.
+    transform: Option<String>,
+    /// The area of the layout covered by the output, in logical coordinates.
+    rect: SwayRect,
+    modes: Option<Vec<SwayMode>>,
+    current_mode: Option<SwayMode>,
+}
+
+#[derive(Deserialize)]
+struct SwayRect {
+    x: i64,
+    y: i64,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct SwayMode {
+    width: u32,
+    height: u32,
+    /// The refresh rate in millihertz.
+    refresh: u32,
+}
+
+/// The reply to a subscription.
+#[derive(Deserialize)]
+struct SubscribeReply {
+    success: bool,
+}
+
+/// Yields the path of the sway IPC socket, which sway announces in `SWAYSOCK`.
+fn socket_from_env() -> Result<PathBuf, Error> {
+    std::env::var_os("SWAYSOCK")
+        .map(PathBuf::from)
+        .ok_or_else(|| Error::new(ErrorKind::NotFound, "SWAYSOCK is not set"))
+}
+
+/// Sends a message of the given type over the sway IPC socket.
+fn send_message(stream: &mut UnixStream, message_type: u32, payload: &[u8]) -> Result<(), Error> {
+    let mut message = IPC_MAGIC.to_vec();
+    message.extend((payload.len() as u32).to_ne_bytes());
+    message.extend(message_type.to_ne_bytes());
+    message.extend(payload);
+
+    stream.write_all(&message)
+}
+
+/// Reads a message from the sway IPC socket, yielding its type and payload.
+fn read_message(stream: &mut UnixStream) -> Result<(u32, String), Error> {
+    let mut header = [0; 14];
+    stream.read_exact(&mut header)?;
+
+    if &header[..6] != IPC_MAGIC {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "invalid message on the sway IPC socket",
+        ));
+    }
+
+    let length = u32::from_ne_bytes(header[6..10].try_into().unwrap());
+    let message_type = u32::from_ne_bytes(header[10..14].try_into().unwrap());
+    let mut payload = vec![0; length as usize];
+    stream.read_exact(&mut payload)?;
+
+    Ok((message_type, String::from_utf8_lossy(&payload).into_owned()))
+}
+
+/// Parses the outputs reported by `swaymsg -t get_outputs`, i.e. the reply to `GET_OUTPUTS`, and
+/// returns the active ones as monitors, in the order they are listed.
+pub fn parse_sway_outputs(json: &str) -> Result<Vec<Monitor>, Error> {
+    let outputs: Vec<SwayOutput> = miniserde::json::from_str(json).map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidData,
+            "invalid sway outputs: expected a list of outputs with a name and a rect",
+        )
+    })?;
+
+    let mut monitors = Vec::new();
+
+    for output in outputs.into_iter().filter(|output| output.active) {
+        let (rotation, reflection) = match output.transform.as_deref() {
+            None | Some("normal") => transform(0, false)?,
+            Some("flipped") => transform(0, true)?,
+            Some(other) => {
+                let (degrees, flipped) = match other.strip_prefix("flipped-") {
+                    Some(degrees) => (degrees, true),
+                    None => (other, false),
+                };
+                let degrees = degrees.parse().map_err(|_| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("invalid transform of {}: {}", output.name, other),
+                    )
+                })?;
+
+                transform(degrees, flipped)?
+            }
+        };
+
+        let scale = match output.scale {
+            Some(factor) => Scale::from_f64(factor).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid scale of {}: {}", output.name, factor),
+                )
+            })?,
+            None => Scale::ONE,
+        };
+
+        let modes = output
+            .modes
+            .unwrap_or_default()
+            .iter()
+            .map(|mode| {
+                Mode::new(mode.width, mode.height, mode.refresh)
+                    .with_current(output.current_mode.as_ref() == Some(mode))
+            })
+            .collect();
+
+        let rect = Rect::new(output.rect.width, output.rect.height, Point::new(0, 0));
+        let monitor = Monitor::new(output.name, monitors.len() as u32, rect)
+            .with_primary(output.primary.unwrap_or(false))
+            .with_rotation(rotation)
+            .with_reflection(reflection)
+            .with_scale(scale)
+            .with_modes(modes);
+
+        monitors.push((monitor, (output.rect.x, output.rect.y)));
+    }
+
+    Ok(shift_to_origin(monitors))
+}
+
+/// This is an implementor for `LoadMonitors` which queries the outputs of sway through its IPC
+/// socket, like `swaymsg -t get_outputs`.
+/// Sway does not report the EDID of its outputs, so the monitors have no identity.
+pub struct SwayMonitorLoader {
+    socket: PathBuf,
+}
+
+impl SwayMonitorLoader {
+    /// Creates an instance of `SwayMonitorLoader` which connects to the socket named by the
+    /// `SWAYSOCK` environment variable; yields an Error if it is not set.
+    pub fn new() -> Result<SwayMonitorLoader, Error> {
+        Ok(Self::with_socket(socket_from_env()?))
+    }
+
+    /// Creates an instance of `SwayMonitorLoader` which connects to the given socket.
+    pub fn with_socket(socket: impl Into<PathBuf>) -> SwayMonitorLoader {
+        SwayMonitorLoader {
+            socket: socket.into(),
+        }
+    }
+
+    /// Yields the path of the IPC socket.
+    pub fn socket(&self) -> &Path {
+        &self.socket
+    }
+}
+
+impl LoadMonitors<Error> for SwayMonitorLoader {
+    /// Queries the outputs of sway and returns the active ones, laid out as in sway.
+    fn load_monitors(&self) -> Result<Vec<Monitor>, Error> {
+        let mut stream = UnixStream::connect(&self.socket)?;
+        send_message(&mut stream, IPC_GET_OUTPUTS, b"")?;
+
+        loop {
+            let (message_type, payload) = read_message(&mut stream)?;
+
+            if message_type == IPC_GET_OUTPUTS {
+                return parse_sway_outputs(&payload);
+            }
+        }
+    }
+}
+
+/// Subscribes to the `output` events by which sway reports changes to its outputs, such as
+/// hotplugged displays or outputs reconfigured by `swaymsg output`.
+pub struct SwayEvents {
+    stream: UnixStream,
+}
+
+impl SwayEvents {
+    /// Connects to the socket named by the `SWAYSOCK` environment variable and subscribes to
+    /// `output` events.
+    pub fn new() -> Result<SwayEvents, Error> {
+        Self::with_socket(socket_from_env()?)
+    }
+
+    /// Connects to the given socket and subscribes to `output` events.
+    pub fn with_socket(socket: impl AsRef<Path>) -> Result<SwayEvents, Error> {
+        let mut stream = UnixStream::connect(socket)?;
+        send_message(&mut stream, IPC_SUBSCRIBE, br#"["output"]"#)?;
+
+        let (_, payload) = read_message(&mut stream)?;
+        let reply: SubscribeReply = miniserde::json::from_str(&payload)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "invalid reply to the subscription"))?;
+
+        if reply.success {
+            Ok(SwayEvents { stream })
+        } else {
+            Err(Error::other(
+                "sway rejected the subscription to output events",
+            ))
+        }
+    }
+
+    /// Blocks until sway reports a change to its outputs. The events do not describe the change,
+    /// so the outputs have to be queried again.
+    pub fn wait_for_change(&mut self) -> Result<(), Error> {
+        loop {
+            let (message_type, _) = read_message(&mut self.stream)?;
+
+            if message_type == IPC_OUTPUT_EVENT {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -1,53 +1,24 @@
-use crate::{LoadMonitors, Monitor, Point, Rect};
+use crate::{
+    ConnectedOutput, InputMatrix, LoadMonitors, LoadOutputs, Monitor, MonitorSetup, OutputInfo,
+    Point, Rect, Scale,
+};
 
-use std::process::Command;
+mod events;
+pub use events::XRandrEvents;
 
-use once_cell::sync::OnceCell;
-use regex::Regex;
+mod parser;
+pub use parser::{
+    parse_xrandr_connected_outputs, parse_xrandr_output, parse_xrandr_outputs, XRandrParseError,
+};
 
-use std::io::{Error, ErrorKind};
+mod window;
+pub use window::{FrameExtents, X11Windows};
 
-fn xrandr_display_information_regex() -> &'static Regex {
-    static XRANDR_DISPLAY_INFORMATION_REGEX: OnceCell<Regex> = OnceCell::new();
-    XRANDR_DISPLAY_INFORMATION_REGEX.get_or_init(|| {
-        Regex::new(
-            r"(?x) # ignore whitespace
-            # [[:alpha:]] represents ascii letters
-            ^([[:alpha:]]+-[[:digit:]]+) # 0 : the adapter name
-            \ # space
-            # 1 : 'disconnected' or 'connected ...'
-            (
-                disconnected
-                |
-                connected
-                \ # space
-                .*? # optional other words
-                ([[:digit:]]+) # 2 : width
-                x
-                ([[:digit:]]+) # 3 : height
-                \+
-                ([[:digit:]]+) # 4 : x_offset
-                \+
-                ([[:digit:]]+) # 5 : y_offset
-            )
-            ",
-        )
-        .unwrap()
-    })
-}
+use std::process::Command;
 
-fn xrandr_crtc_regex() -> &'static Regex {
-    static XRANDR_CRTC_REGEX: OnceCell<Regex> = OnceCell::new();
-    XRANDR_CRTC_REGEX.get_or_init(|| {
-        Regex::new(
-            r"(?x) # ignore whitespace
-        # NOTE: for some reason the [:digit:] needs to be enclosed in more
-        ^(\ |\t)+CRTC: (\ |\t)+([[:digit:]]) # 3 : the crtc number
-        ",
-        )
-        .unwrap()
-    })
-}
+use x11rb::protocol::xinerama::ConnectionExt as _;
+
+use std::io::{Error, ErrorKind};
 
 /// This is an implementor for `LoadMonitors` which uses the `xrandr` command-line interface to
 /// load the list of monitors.
@@ -67,72 +38,154 @@ impl XRandrMonitorLoader {
                 let exit_message = if let Some(code) = code {
                     format!("exit code {}", code)
                 } else {
-                    format!("no exit code")
+                    "no exit code".to_owned()
                 };
 
-                Err(Error::new(
-                    ErrorKind::Other,
-                    format!("xrandr returned with {}", exit_message),
-                ))
+                Err(Error::other(format!(
+                    "xrandr returned with {}",
+                    exit_message
+                )))
             }
         }
     }
 }
 
-/// Given a line from the output of `xrandr --query`, attempts to extract a `Monitor` specification
-/// from it.
-fn try_monitor_from_xrandr_line(xrandr_line: &str) -> Option<Monitor> {
-    // eDP-1 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 344mm x 193mm
-    // HDMI-1 connected 1280x1024+1920+28 (normal left inverted right x axis y axis) 338mm x 270mm
-    // <adapter> connected [primary] <width>x<height>+<x offset>+<y offset> (<flags>) <something>mm x <something else>mm
-    let captures = xrandr_display_information_regex().captures(xrandr_line);
+impl XRandrMonitorLoader {
+    /// Yields every connected output along with the identity of its display, including outputs
+    /// which are connected but not currently displaying anything, such as a monitor which was
+    /// just plugged in.
+    /// Unlike `load_monitors`, this makes the X server probe the outputs again.
+    pub fn connected_outputs(&self) -> Result<Vec<ConnectedOutput>, Error> {
+        let output = Command::new("xrandr").arg("--verbose").output()?;
+
+        parse_xrandr_connected_outputs(&String::from_utf8_lossy(&output.stdout))
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error))
+    }
+}
 
-    if let Some(captures) = captures {
-        // 0 points to the entire match, so skip
-        let adapter_name = captures.get(1).unwrap().as_str().to_owned();
+impl MonitorSetup {
+    /// Yields the arguments to `xrandr` which reproduce this setup, configuring the mode,
+    /// position, rotation, reflection and primary status of each output.
+    /// Outputs which are not part of the setup are left untouched.
+    pub fn to_xrandr_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        for monitor in &self.monitors {
+            let rect = &monitor.rect;
+            // the mode refers to the panel before rotation and scaling
+            let (width, height) = (
+                monitor.scale().to_physical(rect.width),
+                monitor.scale().to_physical(rect.height),
+            );
+            let (width, height) = if monitor.rotation.is_sideways() {
+                (height, width)
+            } else {
+                (width, height)
+            };
 
-        let parse_int = |num: regex::Match| num.as_str().parse::<u32>().map_err(|_| ());
+            for member in monitor.members() {
+                args.extend([
+                    "--output".to_owned(),
+                    member.to_owned(),
+                    "--mode".to_owned(),
+                    format!("{}x{}", width, height),
+                    "--pos".to_owned(),
+                    format!("{}x{}", rect.offset.x(), rect.offset.y()),
+                    "--rotate".to_owned(),
+                    monitor.rotation.name().to_owned(),
+                    "--reflect".to_owned(),
+                    monitor.reflection().name().to_owned(),
+                ]);
+
+                // xrandr scales the mode down to the layout, which is the inverse of the `Scale`
+                if monitor.scale() != Scale::ONE {
+                    let factor = 1.0 / monitor.scale().to_f64();
+                    args.extend(["--scale".to_owned(), format!("{}x{}", factor, factor)]);
+                }
 
-        (|| {
-            match captures.get(2).map(|capture| capture.as_str()) {
-                Some("disconnected") | None => return Err(()),
-                _ => (),
-            };
+                if monitor.primary && member == monitor.name {
+                    args.push("--primary".to_owned());
+                }
+            }
+        }
 
-            let monitor_rectangle = {
-                let width = parse_int(captures.get(3).unwrap())?;
-                let height = parse_int(captures.get(4).unwrap())?;
-                let x_offset = parse_int(captures.get(5).unwrap())?;
-                let y_offset = parse_int(captures.get(6).unwrap())?;
+        args
+    }
 
-                let offset = Point::new(x_offset, y_offset);
+    /// Applies this setup to the current display by invoking `xrandr` with the arguments yielded
+    /// by `to_xrandr_args`.
+    pub fn apply_with_xrandr(&self) -> Result<(), Error> {
+        let status = Command::new("xrandr")
+            .args(self.to_xrandr_args())
+            .status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::other(format!("xrandr returned with {}", status)))
+        }
+    }
+}
 
-                Rect {
-                    width,
-                    height,
-                    offset,
-                }
-            };
+impl InputMatrix {
+    /// Yields the arguments to `xinput` which set this matrix as the Coordinate Transformation
+    /// Matrix of the given device, which may be a name or an ID as listed by `xinput list`.
+    pub fn to_xinput_args(&self, device: &str) -> Vec<String> {
+        let mut args = vec![
+            "set-prop".to_owned(),
+            device.to_owned(),
+            "Coordinate Transformation Matrix".to_owned(),
+        ];
+        args.extend(self.to_string().split(' ').map(str::to_owned));
+
+        args
+    }
 
-            // set CRTC to 0 to begin with
-            Ok(Monitor::new(adapter_name, 0, monitor_rectangle))
-        })()
-        .ok()
-    } else {
-        None
+    /// Sets this matrix on the given device by invoking `xinput` with the arguments yielded by
+    /// `to_xinput_args`.
+    pub fn apply_with_xinput(&self, device: &str) -> Result<(), Error> {
+        let status = Command::new("xinput")
+            .args(self.to_xinput_args(device))
+            .status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::other(format!("xinput returned with {}", status)))
+        }
     }
 }
 
-/// Given a line from the output of `xrandr --query --verbose`, attempts to extract a `CRTC`
-/// specification from it.
-fn try_crtc_from_xrandr_line(xrandr_line: &str) -> Option<u32> {
-    xrandr_crtc_regex().captures(xrandr_line).map(|captures| {
-        let crtc_number_string = captures.get(3).expect("Capture must have a 3rd item");
-        crtc_number_string
-            .as_str()
-            .parse()
-            .expect("CRTC number must be parsable")
-    })
+/// Queries the list of Xinerama screens from the X server, in order.
+fn query_xinerama_screens() -> Result<Vec<Rect>, Error> {
+    let (connection, _) = x11rb::connect(None).map_err(Error::other)?;
+    let reply = connection
+        .xinerama_query_screens()
+        .map_err(Error::other)?
+        .reply()
+        .map_err(Error::other)?;
+
+    Ok(reply
+        .screen_info
+        .iter()
+        .map(|screen| Rect {
+            width: screen.width as u32,
+            height: screen.height as u32,
+            offset: Point::new(screen.x_org.max(0) as u32, screen.y_org.max(0) as u32),
+        })
+        .collect())
+}
+
+impl LoadOutputs<Error> for XRandrMonitorLoader {
+    /// Parses `xrandr --verbose` output and returns every output, including disconnected outputs.
+    /// Unlike `load_monitors`, this makes the X server probe the outputs again, so that displays
+    /// which were just plugged in are listed.
+    fn load_outputs(&self) -> Result<Vec<OutputInfo>, Error> {
+        let output = Command::new("xrandr").arg("--verbose").output()?;
+
+        parse_xrandr_outputs(&String::from_utf8_lossy(&output.stdout))
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error))
+    }
 }
 
 impl LoadMonitors<Error> for XRandrMonitorLoader {
@@ -143,22 +196,14 @@ impl LoadMonitors<Error> for XRandrMonitorLoader {
         xrandr_current.arg("--verbose");
         let command_output = xrandr_current.output()?;
 
-        // the '&' operator dereferences ascii_code so that it can be compared with a regular u8
-        // its original type is &u8
-        let output_lines = command_output
-            .stdout
-            .split(|&ascii_code| ascii_code == b'\n');
-
-        let mut monitors: Vec<Monitor> = Vec::new();
-
-        for line in output_lines {
-            // if valid UTF-8, pass to Monitor
-            if let Ok(line) = std::str::from_utf8(line) {
-                if let Some(monitor) = try_monitor_from_xrandr_line(line) {
-                    monitors.push(monitor)
-                } else if let Some(crtc) = try_crtc_from_xrandr_line(line) {
-                    // assign crtc number to the latest display
-                    monitors.last_mut().expect("Vector must not be empty").crtc = crtc;
+        let mut monitors = parse_xrandr_output(&String::from_utf8_lossy(&command_output.stdout))
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+
+        // if Xinerama is unavailable, the `MonitorSetup` derives the indices instead
+        if let Ok(screens) = query_xinerama_screens() {
+            for monitor in &mut monitors {
+                if let Some(index) = screens.iter().position(|screen| *screen == monitor.rect) {
+                    monitor.xinerama_index = Some(index as u32);
                 }
             }
         }
@@ -0,0 +1,70 @@
+use std::io::Error;
+
+use x11rb::connection::Connection;
+use x11rb::protocol::randr::{ConnectionExt, NotifyMask};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+
+/// The oldest RandR version sending `RROutputChangeNotify` and `RRCrtcChangeNotify` events.
+const RANDR_VERSION: (u32, u32) = (1, 2);
+
+/// Subscribes to the events by which the RandR extension reports changes to the monitor layout,
+/// such as hotplugged displays or outputs moved by `xrandr`.
+pub struct XRandrEvents {
+    connection: RustConnection,
+}
+
+impl XRandrEvents {
+    /// Connects to the X11 display named by the `DISPLAY` environment variable and subscribes to
+    /// `RRScreenChangeNotify`, `RROutputChangeNotify` and `RRCrtcChangeNotify` events.
+    pub fn new() -> Result<XRandrEvents, Error> {
+        let (connection, screen_num) = x11rb::connect(None).map_err(Error::other)?;
+        let root = connection.setup().roots[screen_num].root;
+
+        // the server only sends the events of the version the client announces
+        connection
+            .randr_query_version(RANDR_VERSION.0, RANDR_VERSION.1)
+            .map_err(Error::other)?
+            .reply()
+            .map_err(Error::other)?;
+        connection
+            .randr_select_input(
+                root,
+                NotifyMask::SCREEN_CHANGE | NotifyMask::OUTPUT_CHANGE | NotifyMask::CRTC_CHANGE,
+            )
+            .map_err(Error::other)?
+            .check()
+            .map_err(Error::other)?;
+
+        Ok(XRandrEvents { connection })
+    }
+
+    /// Blocks until the layout changes. A single change usually yields several events, e.g. one
+    /// per affected output and CRTC, so any events which are already queued are consumed as well.
+    pub fn wait_for_change(&self) -> Result<(), Error> {
+        loop {
+            let event = self.connection.wait_for_event().map_err(Error::other)?;
+
+            if is_layout_change(&event) {
+                break;
+            }
+        }
+
+        while self
+            .connection
+            .poll_for_event()
+            .map_err(Error::other)?
+            .is_some()
+        {}
+
+        Ok(())
+    }
+}
+
+/// Returns `true` for the events which are sent when the layout changes.
+fn is_layout_change(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::RandrScreenChangeNotify(_) | Event::RandrNotify(_)
+    )
+}
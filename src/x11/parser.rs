@@ -0,0 +1,555 @@
+use crate::{
+    ConnectedOutput, ConnectionState, Mode, Monitor, MonitorIdentity, MonitorUtilsError,
+    OutputInfo, Rect, Reflection, Rotation, Scale,
+};
+
+use once_cell::sync::OnceCell;
+use regex::Regex;
+use thiserror::Error;
+
+fn xrandr_screen_regex() -> &'static Regex {
+    static XRANDR_SCREEN_REGEX: OnceCell<Regex> = OnceCell::new();
+    XRANDR_SCREEN_REGEX.get_or_init(|| Regex::new(r"^Screen [[:digit:]]+:").unwrap())
+}
+
+fn xrandr_output_header_regex() -> &'static Regex {
+    static XRANDR_OUTPUT_HEADER_REGEX: OnceCell<Regex> = OnceCell::new();
+    XRANDR_OUTPUT_HEADER_REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?x) # ignore whitespace
+            # 1 : the adapter name, which depends on the driver, e.g. 'eDP-1', 'eDP1', 'eDP',
+            # 'HDMI-A-0', 'DisplayPort-0', 'DP-1-1', 'DP-0.8', 'DVI-I-1-1' or 'Virtual1'
+            ^([^[:space:]]+)
+            \ # space
+            (connected|disconnected|unknown\ connection) # 2 : the connection state
+            (\ primary)? # 3 : present if the output is the primary output
+            # 4 : the geometry, e.g. 1920x1080+0+0, which is parsed by `Rect::from_str`; outputs
+            # which are disabled have none, and continue with the list of rotations instead
+            (?:\ ([^[:space:](]+))?
+            (?:\ \(0x[[:xdigit:]]+\))? # the mode ID, only present with --verbose
+            (?:\ (normal|left|inverted|right))? # 5 : the rotation, omitted if normal
+            (?:\ (X\ axis|Y\ axis|X\ and\ Y\ axis))? # 6 : the reflection, omitted if none
+            (?:\ \(([^)]*)\))? # 7 : the supported rotations and reflections
+            (?:\ ([[:digit:]]+)mm\ x\ ([[:digit:]]+)mm)? # 8, 9 : the physical size, if known
+            ",
+        )
+        .unwrap()
+    })
+}
+
+fn xrandr_crtc_regex() -> &'static Regex {
+    static XRANDR_CRTC_REGEX: OnceCell<Regex> = OnceCell::new();
+    XRANDR_CRTC_REGEX.get_or_init(|| {
+        Regex::new(r"^[[:space:]]+CRTC:[[:space:]]+([^[:space:]]+)[[:space:]]*$").unwrap()
+    })
+}
+
+fn xrandr_possible_crtcs_regex() -> &'static Regex {
+    static XRANDR_POSSIBLE_CRTCS_REGEX: OnceCell<Regex> = OnceCell::new();
+    XRANDR_POSSIBLE_CRTCS_REGEX.get_or_init(|| Regex::new(r"^[[:space:]]+CRTCs:(.*)$").unwrap())
+}
+
+fn xrandr_verbose_mode_regex() -> &'static Regex {
+    static XRANDR_VERBOSE_MODE_REGEX: OnceCell<Regex> = OnceCell::new();
+    XRANDR_VERBOSE_MODE_REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?x) # ignore whitespace
+            # 1920x1080 (0x47) 148.500MHz +HSync +VSync *current +preferred
+            ^\ +[^[:space:]]+ # the name, which is usually but not always the resolution
+            \ \(0x[[:xdigit:]]+\) # the mode ID
+            \ +[[:digit:].]+MHz # the pixel clock
+            (.*)$ # 1 : the flags
+            ",
+        )
+        .unwrap()
+    })
+}
+
+fn xrandr_mode_width_regex() -> &'static Regex {
+    static XRANDR_MODE_WIDTH_REGEX: OnceCell<Regex> = OnceCell::new();
+    XRANDR_MODE_WIDTH_REGEX.get_or_init(|| Regex::new(r"^ +h: width +([^[:space:]]+)").unwrap())
+}
+
+fn xrandr_mode_height_regex() -> &'static Regex {
+    static XRANDR_MODE_HEIGHT_REGEX: OnceCell<Regex> = OnceCell::new();
+    XRANDR_MODE_HEIGHT_REGEX.get_or_init(|| {
+        Regex::new(r"^ +v: height +([^[:space:]]+) .* clock +([^[:space:]]+)Hz$").unwrap()
+    })
+}
+
+fn xrandr_brief_mode_regex() -> &'static Regex {
+    static XRANDR_BRIEF_MODE_REGEX: OnceCell<Regex> = OnceCell::new();
+    XRANDR_BRIEF_MODE_REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?x) # ignore whitespace
+            # 1920x1080     60.00*+  59.94    50.00
+            ^\ +([[:digit:]]+)x([[:digit:]]+)(i)?[^[:space:]]* # 1, 2, 3 : width, height, interlaced
+            ((?:\ +[[:digit:].]*[*+]*)+)$ # 4 : the refresh rates, marked with '*' and '+'
+            ",
+        )
+        .unwrap()
+    })
+}
+
+/// The errors yielded when the output of `xrandr` cannot be parsed.
+/// Lines are numbered from 1.
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+pub enum XRandrParseError {
+    #[error("line {}: expected a screen or an output, found {:?}", .line, .content)]
+    UnexpectedLine { line: usize, content: String },
+
+    #[error("line {}: {:?} does not belong to any output", .line, .content)]
+    OrphanedLine { line: usize, content: String },
+
+    #[error(
+        "line {}: invalid geometry {:?} of output {}: {}",
+        .line,
+        .geometry,
+        .output,
+        .reason
+    )]
+    InvalidGeometry {
+        line: usize,
+        output: String,
+        geometry: String,
+        reason: String,
+    },
+
+    #[error("line {}: invalid CRTC {:?} of output {}", .line, .crtc, .output)]
+    InvalidCrtc {
+        line: usize,
+        output: String,
+        crtc: String,
+    },
+
+    #[error("line {}: invalid mode {:?} of output {}", .line, .content, .output)]
+    InvalidMode {
+        line: usize,
+        output: String,
+        content: String,
+    },
+}
+
+impl XRandrParseError {
+    /// Yields the number of the line which could not be parsed, starting from 1.
+    pub fn line(&self) -> usize {
+        match self {
+            XRandrParseError::UnexpectedLine { line, .. }
+            | XRandrParseError::OrphanedLine { line, .. }
+            | XRandrParseError::InvalidGeometry { line, .. }
+            | XRandrParseError::InvalidCrtc { line, .. }
+            | XRandrParseError::InvalidMode { line, .. } => *line,
+        }
+    }
+}
+
+/// An output as listed by `xrandr`, along with the properties read so far.
+struct OutputBlock {
+    name: String,
+    connection: ConnectionState,
+    /// The geometry of the output, if it is enabled.
+    rect: Option<Rect>,
+    primary: bool,
+    rotation: Rotation,
+    reflection: Reflection,
+    crtc: Option<u32>,
+    possible_crtcs: Vec<u32>,
+    rotations: Vec<Rotation>,
+    physical_size: Option<(u32, u32)>,
+    identity: Option<MonitorIdentity>,
+    modes: Vec<Mode>,
+}
+
+impl OutputBlock {
+    /// Derives the scale set with `xrandr --scale` from the ratio between the current mode and the
+    /// geometry of the output, which is the mode scaled down to logical coordinates.
+    fn scale(&self) -> Option<Scale> {
+        let rect = self.rect.as_ref().filter(|rect| rect.width() > 0)?;
+        let mode = self.modes.iter().find(|mode| mode.is_current())?;
+        let mode_width = if self.rotation.is_sideways() {
+            mode.height()
+        } else {
+            mode.width()
+        };
+
+        Scale::from_f64(mode_width as f64 / rect.width() as f64)
+    }
+
+    fn into_output_info(self) -> OutputInfo {
+        let scale = self.scale();
+        let mut output = OutputInfo::new(self.name, self.connection)
+            .with_primary(self.primary)
+            .with_rotation(self.rotation)
+            .with_reflection(self.reflection)
+            .with_possible_crtcs(self.possible_crtcs)
+            .with_rotations(self.rotations)
+            .with_modes(self.modes);
+
+        if let Some(scale) = scale {
+            output = output.with_scale(scale);
+        }
+        if let Some(rect) = self.rect {
+            output = output.with_rect(rect);
+        }
+        if let Some(crtc) = self.crtc {
+            output = output.with_crtc(crtc);
+        }
+        // xrandr reports 0mm x 0mm if the size is unknown
+        if let Some((width, height)) = self.physical_size.filter(|&(w, h)| w > 0 && h > 0) {
+            output = output.with_physical_size(width, height);
+        }
+        if let Some(identity) = self.identity {
+            output = output.with_identity(identity);
+        }
+
+        output
+    }
+}
+
+/// The part of an output block which is being read.
+enum Section {
+    /// The header, the properties or the modes of the output.
+    Properties,
+    /// The dump of the `EDID` property read so far, indented by two tabs.
+    Edid(String),
+    /// A mode listed with `--verbose`, whose resolution and refresh rate follow on the `h:` and
+    /// `v:` lines.
+    Mode(PendingMode),
+}
+
+/// The part of a mode read so far.
+struct PendingMode {
+    current: bool,
+    preferred: bool,
+    interlaced: bool,
+    width: Option<u32>,
+}
+
+/// A line-oriented parser for the output of `xrandr --query`, which reads one output block at a
+/// time. Each block starts with an unindented header, followed by the properties of the output
+/// (only with `--verbose`) and its modes.
+struct XRandrParser {
+    outputs: Vec<OutputBlock>,
+    section: Section,
+}
+
+impl XRandrParser {
+    fn new() -> XRandrParser {
+        XRandrParser {
+            outputs: Vec::new(),
+            section: Section::Properties,
+        }
+    }
+
+    fn parse(mut self, xrandr_output: &str) -> Result<Vec<OutputBlock>, XRandrParseError> {
+        // whether a screen or an output was read, which lines other than headers belong to
+        let mut in_block = false;
+
+        for (index, line) in xrandr_output.lines().enumerate() {
+            let number = index + 1;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if !line.starts_with([' ', '\t']) {
+                self.finish_edid();
+
+                if xrandr_screen_regex().is_match(line) {
+                    // the screen itself has no properties, so only its outputs are of interest
+                    in_block = true;
+                } else if let Some(output) = parse_output_header(line, number)? {
+                    self.outputs.push(output);
+                    self.section = Section::Properties;
+                    in_block = true;
+                } else {
+                    return Err(XRandrParseError::UnexpectedLine {
+                        line: number,
+                        content: line.to_owned(),
+                    });
+                }
+            } else if !in_block {
+                return Err(XRandrParseError::OrphanedLine {
+                    line: number,
+                    content: line.to_owned(),
+                });
+            } else {
+                self.parse_indented_line(line, number)?;
+            }
+        }
+
+        self.finish_edid();
+
+        Ok(self.outputs)
+    }
+
+    /// Reads a property, a row of the EDID dump, or a mode of the current output.
+    fn parse_indented_line(&mut self, line: &str, number: usize) -> Result<(), XRandrParseError> {
+        let trimmed = line.trim();
+
+        // the dump is indented further than the property name, one row of hex digits per line
+        if let Section::Edid(hex) = &mut self.section {
+            if trimmed.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                hex.push_str(trimmed);
+                return Ok(());
+            }
+
+            self.finish_edid();
+        }
+
+        let Some(output) = self.outputs.last_mut() else {
+            // lines of the screen, rather than of an output
+            return Ok(());
+        };
+
+        if line.starts_with('\t') {
+            if trimmed == "EDID:" {
+                self.section = Section::Edid(String::new());
+            } else if let Some(captures) = xrandr_crtc_regex().captures(line) {
+                let crtc = captures[1]
+                    .parse()
+                    .map_err(|_| XRandrParseError::InvalidCrtc {
+                        line: number,
+                        output: output.name.clone(),
+                        crtc: captures[1].to_owned(),
+                    })?;
+
+                // disconnected outputs can still be assigned a CRTC, but are not monitors
+                output.crtc = Some(crtc);
+            } else if let Some(captures) = xrandr_possible_crtcs_regex().captures(line) {
+                output.possible_crtcs = captures[1]
+                    .split_whitespace()
+                    .map(|crtc| {
+                        crtc.parse().map_err(|_| XRandrParseError::InvalidCrtc {
+                            line: number,
+                            output: output.name.clone(),
+                            crtc: crtc.to_owned(),
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+            }
+
+            return Ok(());
+        }
+
+        // the modes are indented by spaces
+        let invalid_mode = || XRandrParseError::InvalidMode {
+            line: number,
+            output: output.name.clone(),
+            content: trimmed.to_owned(),
+        };
+
+        if let Some(captures) = xrandr_verbose_mode_regex().captures(line) {
+            let flags = captures[1].split_whitespace().collect::<Vec<_>>();
+
+            self.section = Section::Mode(PendingMode {
+                current: flags.contains(&"*current"),
+                preferred: flags.contains(&"+preferred"),
+                interlaced: flags.contains(&"Interlace"),
+                width: None,
+            });
+        } else if let Some(captures) = xrandr_mode_width_regex().captures(line) {
+            if let Section::Mode(pending) = &mut self.section {
+                pending.width = Some(captures[1].parse().map_err(|_| invalid_mode())?);
+            }
+        } else if let Some(captures) = xrandr_mode_height_regex().captures(line) {
+            if let Section::Mode(pending) =
+                std::mem::replace(&mut self.section, Section::Properties)
+            {
+                let width = pending.width.ok_or_else(invalid_mode)?;
+                let height = captures[1].parse().map_err(|_| invalid_mode())?;
+                let refresh_rate = parse_refresh_rate(&captures[2]).ok_or_else(invalid_mode)?;
+
+                output.modes.push(
+                    Mode::new(width, height, refresh_rate)
+                        .with_current(pending.current)
+                        .with_preferred(pending.preferred)
+                        .with_interlaced(pending.interlaced),
+                );
+            }
+        } else if let Some(captures) = xrandr_brief_mode_regex().captures(line) {
+            // without --verbose, each line lists every refresh rate of a resolution
+            let width = captures[1].parse().map_err(|_| invalid_mode())?;
+            let height = captures[2].parse().map_err(|_| invalid_mode())?;
+            let interlaced = captures.get(3).is_some();
+
+            for rate in captures[4].split_whitespace() {
+                let markers = rate.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+                let rate = &rate[..rate.len() - markers.len()];
+
+                // the markers of a rate which is preferred but not current are separated by a
+                // space, i.e. the one of the missing '*'
+                if rate.is_empty() {
+                    let mode = output.modes.last_mut().ok_or_else(invalid_mode)?;
+                    *mode = mode.with_preferred(markers.contains('+'));
+                    continue;
+                }
+
+                let refresh_rate = parse_refresh_rate(rate).ok_or_else(invalid_mode)?;
+                output.modes.push(
+                    Mode::new(width, height, refresh_rate)
+                        .with_current(markers.contains('*'))
+                        .with_preferred(markers.contains('+'))
+                        .with_interlaced(interlaced),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the EDID dump of the current output, if one is being read.
+    /// Displays with an EDID which cannot be decoded are left without an identity, rather than
+    /// failing the whole parse.
+    fn finish_edid(&mut self) {
+        if let Section::Edid(hex) = std::mem::replace(&mut self.section, Section::Properties) {
+            let identity =
+                edid_from_hex(&hex).and_then(|edid| MonitorIdentity::from_edid(&edid).ok());
+
+            if let Some(output) = self.outputs.last_mut() {
+                output.identity = identity;
+            }
+        }
+    }
+}
+
+/// Given an unindented line from the output of `xrandr --query`, attempts to extract an output
+/// from it, along with the `Monitor` it displays if it is enabled.
+fn parse_output_header(line: &str, number: usize) -> Result<Option<OutputBlock>, XRandrParseError> {
+    // eDP-1 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 344mm x 193mm
+    // HDMI-1 connected 1280x1024+1920+28 (0x4a) left (normal left inverted right x axis y axis) 338mm x 270mm
+    // DP-1 disconnected (normal left inverted right x axis y axis)
+    // <adapter> <state> [primary] [<width>x<height>+<x offset>+<y offset> [(<mode id>)] [<rotation>]] (<flags>) ...
+    let Some(captures) = xrandr_output_header_regex().captures(line) else {
+        return Ok(None);
+    };
+
+    // 0 points to the entire match, so skip
+    let name = captures[1].to_owned();
+    let connection = match &captures[2] {
+        "connected" => ConnectionState::Connected,
+        "disconnected" => ConnectionState::Disconnected,
+        _ => ConnectionState::Unknown,
+    };
+    let primary = captures.get(3).is_some();
+
+    let rect = match captures.get(4).map(|capture| capture.as_str()) {
+        Some(geometry) => Some(geometry.parse::<Rect>().map_err(|error| {
+            let reason = match error {
+                MonitorUtilsError::InvalidGeometry { reason, .. } => reason,
+                error => error.to_string(),
+            };
+
+            XRandrParseError::InvalidGeometry {
+                line: number,
+                output: name.clone(),
+                geometry: geometry.to_owned(),
+                reason,
+            }
+        })?),
+        None => None,
+    };
+
+    let rotation = captures
+        .get(5)
+        .and_then(|capture| capture.as_str().parse().ok())
+        .unwrap_or(Rotation::Normal);
+
+    let reflection = captures
+        .get(6)
+        .and_then(|capture| capture.as_str().parse().ok())
+        .unwrap_or(Reflection::Normal);
+
+    let rotations = captures
+        .get(7)
+        .map(|capture| {
+            capture
+                .as_str()
+                .split_whitespace()
+                // the reflections ('x axis' and 'y axis') are not rotations
+                .filter_map(|rotation| rotation.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let physical_size = match (captures.get(8), captures.get(9)) {
+        (Some(width), Some(height)) => width
+            .as_str()
+            .parse()
+            .ok()
+            .zip(height.as_str().parse().ok()),
+        _ => None,
+    };
+
+    Ok(Some(OutputBlock {
+        name,
+        connection,
+        rect,
+        primary,
+        rotation,
+        reflection,
+        crtc: None,
+        possible_crtcs: Vec::new(),
+        rotations,
+        physical_size,
+        identity: None,
+        modes: Vec::new(),
+    }))
+}
+
+/// Parses a refresh rate as printed by `xrandr`, e.g. `59.94`, into millihertz.
+fn parse_refresh_rate(rate: &str) -> Option<u32> {
+    let hertz = rate.parse::<f64>().ok()?;
+
+    (hertz.is_finite() && hertz >= 0.0 && hertz < u32::MAX as f64 / 1000.0)
+        .then(|| (hertz * 1000.0).round() as u32)
+}
+
+/// Decodes the hexadecimal dump of an EDID, as printed by `xrandr --verbose`.
+fn edid_from_hex(hex: &str) -> Option<Vec<u8>> {
+    let pairs = hex.as_bytes().chunks_exact(2);
+
+    if !pairs.remainder().is_empty() {
+        return None;
+    }
+
+    pairs
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+/// Parses the output of `xrandr --verbose` into the list of every output, in the order they are
+/// listed, including disconnected outputs and outputs which are connected but disabled.
+pub fn parse_xrandr_outputs(xrandr_output: &str) -> Result<Vec<OutputInfo>, XRandrParseError> {
+    let outputs = XRandrParser::new().parse(xrandr_output)?;
+
+    Ok(outputs
+        .into_iter()
+        .map(OutputBlock::into_output_info)
+        .collect())
+}
+
+/// Parses the output of `xrandr --verbose` (or `xrandr --current --verbose`) into the list of
+/// enabled monitors, in the order they are listed, along with their CRTC, rotation, identity and
+/// modes.
+/// Without `--verbose`, the CRTCs and identities are missing.
+/// The Xinerama indices are left unset, since they cannot be derived from the output.
+pub fn parse_xrandr_output(xrandr_output: &str) -> Result<Vec<Monitor>, XRandrParseError> {
+    let outputs = parse_xrandr_outputs(xrandr_output)?;
+
+    // outputs which were unplugged while enabled keep their geometry, but display nothing
+    Ok(outputs.iter().filter_map(OutputInfo::to_monitor).collect())
+}
+
+/// Parses the output of `xrandr --verbose` into the list of connected outputs, including outputs
+/// which are connected but disabled, along with the identities of their displays.
+pub fn parse_xrandr_connected_outputs(
+    xrandr_output: &str,
+) -> Result<Vec<ConnectedOutput>, XRandrParseError> {
+    let outputs = parse_xrandr_outputs(xrandr_output)?;
+
+    Ok(outputs
+        .iter()
+        .filter_map(OutputInfo::to_connected_output)
+        .collect())
+}
@@ -0,0 +1,237 @@
+use crate::{Point, Rect};
+
+use std::io::Error;
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    AtomEnum, ClientMessageEvent, ConfigureWindowAux, ConnectionExt, EventMask, Window,
+};
+use x11rb::rust_connection::RustConnection;
+
+x11rb::atom_manager! {
+    /// Atoms used for querying windows through EWMH.
+    Atoms: AtomsCookie {
+        _NET_ACTIVE_WINDOW,
+        _NET_FRAME_EXTENTS,
+        _NET_MOVERESIZE_WINDOW,
+        _NET_SUPPORTED,
+    }
+}
+
+/// Converts any error yielded by `x11rb` into an `std::io::Error`, so that it matches the error
+/// type used by the rest of the module.
+fn to_io_error(error: impl std::error::Error + Send + Sync + 'static) -> Error {
+    Error::other(error)
+}
+
+/// `NorthWestGravity`, which makes the requested position refer to the top-left corner of the
+/// frame.
+const NORTH_WEST_GRAVITY: u32 = 1;
+/// Flags for `_NET_MOVERESIZE_WINDOW` indicating that x, y, width and height are all present.
+const MOVERESIZE_XYWH: u32 = 0b1111 << 8;
+/// Flag for `_NET_MOVERESIZE_WINDOW` indicating that the request comes from a pager or similar
+/// tool acting on behalf of the user, which window managers should honor.
+const MOVERESIZE_SOURCE_PAGER: u32 = 2 << 12;
+
+/// Size of the decorations that the window manager draws around a window, as reported by
+/// `_NET_FRAME_EXTENTS`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct FrameExtents {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+}
+
+/// Provides queries on the windows of an X11 display, using the EWMH hints set by the window
+/// manager.
+pub struct X11Windows {
+    connection: RustConnection,
+    root: Window,
+    atoms: Atoms,
+}
+
+impl X11Windows {
+    /// Connects to the X11 display named by the `DISPLAY` environment variable.
+    pub fn new() -> Result<X11Windows, Error> {
+        let (connection, screen_num) = x11rb::connect(None).map_err(to_io_error)?;
+        let root = connection.setup().roots[screen_num].root;
+        let atoms = Atoms::new(&connection)
+            .map_err(to_io_error)?
+            .reply()
+            .map_err(to_io_error)?;
+
+        Ok(X11Windows {
+            connection,
+            root,
+            atoms,
+        })
+    }
+
+    /// Yields the ID of the currently focused window, as reported by `_NET_ACTIVE_WINDOW`.
+    pub fn active_window(&self) -> Result<u32, Error> {
+        let reply = self
+            .connection
+            .get_property(
+                false,
+                self.root,
+                self.atoms._NET_ACTIVE_WINDOW,
+                AtomEnum::WINDOW,
+                0,
+                1,
+            )
+            .map_err(to_io_error)?
+            .reply()
+            .map_err(to_io_error)?;
+
+        reply
+            .value32()
+            .and_then(|mut values| values.next())
+            .filter(|&window| window != x11rb::NONE)
+            .ok_or_else(|| Error::other("window manager did not report an active window"))
+    }
+
+    /// Yields the extents of the frame drawn around the window by the window manager.
+    /// If the window manager does not set `_NET_FRAME_EXTENTS`, the extents are all zero.
+    pub fn frame_extents(&self, window: u32) -> Result<FrameExtents, Error> {
+        let reply = self
+            .connection
+            .get_property(
+                false,
+                window,
+                self.atoms._NET_FRAME_EXTENTS,
+                AtomEnum::CARDINAL,
+                0,
+                4,
+            )
+            .map_err(to_io_error)?
+            .reply()
+            .map_err(to_io_error)?;
+
+        let extents = reply
+            .value32()
+            .map(|values| values.collect::<Vec<u32>>())
+            .unwrap_or_default();
+
+        match extents[..] {
+            [left, right, top, bottom] => Ok(FrameExtents {
+                left,
+                right,
+                top,
+                bottom,
+            }),
+            _ => Ok(FrameExtents::default()),
+        }
+    }
+
+    /// Yields the rectangle occupied by the window and its frame on the virtual screen.
+    /// Portions of the window lying above or to the left of the virtual screen are cut off, since
+    /// a `Rect` cannot have a negative offset.
+    pub fn frame_rect(&self, window: u32) -> Result<Rect, Error> {
+        let geometry = self
+            .connection
+            .get_geometry(window)
+            .map_err(to_io_error)?
+            .reply()
+            .map_err(to_io_error)?;
+
+        // the geometry is relative to the parent, which is usually the window manager's frame
+        let translated = self
+            .connection
+            .translate_coordinates(window, self.root, 0, 0)
+            .map_err(to_io_error)?
+            .reply()
+            .map_err(to_io_error)?;
+
+        let extents = self.frame_extents(window)?;
+
+        let x = translated.dst_x as i64 - extents.left as i64;
+        let y = translated.dst_y as i64 - extents.top as i64;
+        let width = geometry.width as i64 + (extents.left + extents.right) as i64;
+        let height = geometry.height as i64 + (extents.top + extents.bottom) as i64;
+
+        // clip against the top-left corner of the virtual screen
+        let clipped_width = (width + x.min(0)).max(0);
+        let clipped_height = (height + y.min(0)).max(0);
+
+        Ok(Rect::new(
+            clipped_width as u32,
+            clipped_height as u32,
+            Point::new(x.max(0) as u32, y.max(0) as u32),
+        ))
+    }
+
+    /// Returns `true` if the window manager advertises support for the given atom in
+    /// `_NET_SUPPORTED`.
+    fn supports(&self, atom: u32) -> Result<bool, Error> {
+        let reply = self
+            .connection
+            .get_property(
+                false,
+                self.root,
+                self.atoms._NET_SUPPORTED,
+                AtomEnum::ATOM,
+                0,
+                u32::MAX,
+            )
+            .map_err(to_io_error)?
+            .reply()
+            .map_err(to_io_error)?;
+
+        Ok(reply
+            .value32()
+            .map(|mut atoms| atoms.any(|supported| supported == atom))
+            .unwrap_or(false))
+    }
+
+    /// Moves and resizes the window such that it occupies the given rectangle on the virtual
+    /// screen, including its frame.
+    /// The request is sent through `_NET_MOVERESIZE_WINDOW` if the window manager supports it, and
+    /// otherwise falls back to configuring the window directly.
+    pub fn move_resize(&self, window: u32, rect: &Rect) -> Result<(), Error> {
+        let extents = self.frame_extents(window)?;
+
+        let x = rect.offset().x();
+        let y = rect.offset().y();
+        // the client area cannot be empty, so leave at least a single pixel
+        let width = rect
+            .width()
+            .saturating_sub(extents.left + extents.right)
+            .max(1);
+        let height = rect
+            .height()
+            .saturating_sub(extents.top + extents.bottom)
+            .max(1);
+
+        if self.supports(self.atoms._NET_MOVERESIZE_WINDOW)? {
+            let flags = NORTH_WEST_GRAVITY | MOVERESIZE_XYWH | MOVERESIZE_SOURCE_PAGER;
+            let event = ClientMessageEvent::new(
+                32,
+                window,
+                self.atoms._NET_MOVERESIZE_WINDOW,
+                [flags, x, y, width, height],
+            );
+
+            self.connection
+                .send_event(
+                    false,
+                    self.root,
+                    EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+                    event,
+                )
+                .map_err(to_io_error)?;
+        } else {
+            let aux = ConfigureWindowAux::new()
+                .x((x + extents.left) as i32)
+                .y((y + extents.top) as i32)
+                .width(width)
+                .height(height);
+
+            self.connection
+                .configure_window(window, &aux)
+                .map_err(to_io_error)?;
+        }
+
+        self.connection.flush().map_err(to_io_error)
+    }
+}
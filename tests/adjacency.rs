@@ -0,0 +1,127 @@
+use monitor_utils::{Monitor, MonitorSetup, Point, Rect, Side};
+
+fn monitor(name: &str, width: u32, height: u32, x: u32, y: u32) -> Monitor {
+    Monitor::new(
+        name.to_owned(),
+        0,
+        Rect::new(width, height, Point::new(x, y)),
+    )
+}
+
+/// A laptop panel with a smaller monitor to its right, hanging below it, and a monitor touching
+/// the smaller one only at a corner.
+///
+/// ```text
+/// +--------+         +------+
+/// |        |         | DP-2 |
+/// | eDP-1  +---------+------+
+/// |        | HDMI-1  |
+/// +--------+         |
+///          +---------+
+/// ```
+fn staggered_setup() -> MonitorSetup {
+    MonitorSetup::new(vec![
+        monitor("eDP-1", 1920, 1080, 0, 0),
+        monitor("HDMI-1", 1280, 1024, 1920, 500),
+        monitor("DP-2", 400, 500, 3200, 0),
+    ])
+}
+
+#[test]
+fn partial_edges_are_shared() {
+    let setup = staggered_setup();
+    let laptop = setup.monitor_by_name("eDP-1").unwrap();
+    let external = setup.monitor_by_name("HDMI-1").unwrap();
+    let graph = setup.adjacency();
+
+    let adjacency = graph
+        .neighbors(laptop)
+        .find(|adjacency| adjacency.neighbor() == external)
+        .unwrap();
+    let edge = adjacency.edge();
+    assert_eq!(edge.side(), Side::Right);
+    assert_eq!((edge.start(), edge.end(), edge.length()), (500, 1080, 580));
+
+    // the same edge, from the other side
+    let reverse = graph
+        .neighbors(external)
+        .find(|adjacency| adjacency.neighbor() == laptop)
+        .unwrap();
+    assert_eq!(reverse.edge().side(), Side::Left);
+    assert_eq!(reverse.edge().side(), edge.side().opposite());
+    assert_eq!(reverse.edge().length(), 580);
+
+    assert_eq!(graph.adjacencies().len(), 2);
+    assert_eq!(setup.monitor_right_of(laptop).unwrap(), external);
+    assert_eq!(setup.monitor_left_of(external).unwrap(), laptop);
+}
+
+#[test]
+fn corner_contact_is_not_adjacency() {
+    let setup = staggered_setup();
+    let external = setup.monitor_by_name("HDMI-1").unwrap();
+    let corner = setup.monitor_by_name("DP-2").unwrap();
+    let graph = setup.adjacency();
+
+    assert!(!graph.are_adjacent(external, corner));
+    assert!(!graph.are_adjacent(corner, external));
+    assert_eq!(graph.neighbors(corner).count(), 0);
+
+    assert!(setup.monitor_above(external).is_err());
+    assert!(setup.monitor_right_of(external).is_err());
+    assert!(setup.monitor_below(corner).is_err());
+    assert!(setup.monitor_left_of(corner).is_err());
+}
+
+#[test]
+fn longest_shared_edge_wins_among_several_neighbors() {
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-1", 1280, 1080, 0, 0),
+        monitor("DP-2", 2560, 1080, 1280, 0),
+        monitor("DP-3", 3840, 1080, 0, 1080),
+    ]);
+    let left = setup.monitor_by_name("DP-1").unwrap();
+    let right = setup.monitor_by_name("DP-2").unwrap();
+    let bottom = setup.monitor_by_name("DP-3").unwrap();
+
+    assert_eq!(setup.adjacency().neighbors(bottom).count(), 2);
+    assert_eq!(
+        setup.adjacency().neighbor_on(bottom, Side::Top),
+        Some(right)
+    );
+    assert_eq!(setup.monitor_above(bottom).unwrap(), right);
+    assert_eq!(setup.monitor_below(left).unwrap(), bottom);
+    assert_eq!(setup.monitor_below(right).unwrap(), bottom);
+    assert_eq!(setup.monitor_right_of(left).unwrap(), right);
+    assert!(setup.monitor_above(left).is_err());
+}
+
+#[test]
+fn equal_edges_prefer_the_earliest_neighbor() {
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-2", 1920, 1080, 1920, 0),
+        monitor("DP-1", 1920, 1080, 0, 0),
+        monitor("DP-3", 3840, 1080, 0, 1080),
+    ]);
+    let bottom = setup.monitor_by_name("DP-3").unwrap();
+
+    // DP-1 lies at the origin, so it comes first clockwise
+    assert_eq!(setup.monitor_above(bottom).unwrap().name(), "DP-1");
+}
+
+#[test]
+fn monitors_reaching_past_the_end_of_the_coordinate_space_are_clipped() {
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-1", 10, 10, 0, 0),
+        monitor("DP-2", 10, 10, u32::MAX - 15, 0),
+        monitor("DP-3", 10, 10, u32::MAX - 5, 0),
+    ]);
+    let origin = setup.monitor_by_name("DP-1").unwrap();
+    let near = setup.monitor_by_name("DP-2").unwrap();
+    let far = setup.monitor_by_name("DP-3").unwrap();
+    let graph = setup.adjacency();
+
+    assert!(graph.are_adjacent(near, far));
+    assert_eq!(graph.neighbors(origin).count(), 0);
+    assert_eq!(setup.monitor_right_of(near).unwrap(), far);
+}
@@ -0,0 +1,98 @@
+//! These tests run the `monitor-utils` binary on layouts given on the command line or stored in
+//! `tests/fixtures/diff`, so that they neither need a display server nor touch the cache.
+#![cfg(feature = "cli")]
+
+use std::process::{Command, Output};
+
+fn monitor_utils(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_monitor-utils"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn diff_fixture(name: &str) -> String {
+    format!(
+        "{}/tests/fixtures/diff/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    assert!(!output.status.success(), "{:?}", output);
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn layout_defines_the_monitors() {
+    let output = monitor_utils(&[
+        "--layout",
+        "DP-1:1920x1080+0+0,HDMI-1:1280x1024+1920+0",
+        "--at-point",
+        "2000",
+        "10",
+        "--shell",
+        "--geometry",
+    ]);
+
+    assert_eq!(
+        stdout(&output),
+        "X_OFFSET=1920\nY_OFFSET=0\nWIDTH=1280\nHEIGHT=1024\n"
+    );
+}
+
+#[test]
+fn invalid_layouts_are_rejected() {
+    let rejection = |spec: &str| stderr(&monitor_utils(&["--layout", spec, "--geometry"]));
+
+    assert!(rejection("DP-1").contains("Invalid monitor \"DP-1\"; expected NAME:WxH+X+Y"));
+    assert!(rejection(":1920x1080+0+0").contains("Missing monitor name"));
+    assert!(rejection("DP-1:1920x1080+0+0,DP-1:1920x1080+1920+0")
+        .contains("Duplicate monitor name: DP-1"));
+    assert!(rejection("DP-1:1920x1080+0++0").contains("y offset \"+0\" is not a number"));
+    assert!(rejection("DP-1:1920x1080+0").contains("missing y offset"));
+}
+
+/// The JSON line printed by `diff`, which `watch` prints after each change as well.
+#[test]
+fn diff_prints_the_changes_as_a_json_line() {
+    let output = monitor_utils(&["diff", &diff_fixture("before"), &diff_fixture("after")]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        concat!(
+            r#"{"added":[{"name":"DP-2","geometry":"1280x1024+3360+0"}],"#,
+            r#""removed":[{"name":"DP-1","geometry":"1920x1080+4480+0"}],"#,
+            r#""changed":[{"name":"eDP-1","changes":["primary"],"from":"1920x1080+0+0","#,
+            r#""to":"1920x1080+0+0","rotation":"normal","primary":false},"#,
+            r#"{"name":"HDMI-1","changes":["resized","rotated","primary"],"#,
+            r#""from":"2560x1440+1920+0","to":"1440x2560+1920+0","rotation":"left","#,
+            r#""primary":true}]}"#,
+            "\n"
+        )
+    );
+}
+
+#[test]
+fn diff_of_identical_setups_is_empty() {
+    let output = monitor_utils(&["diff", &diff_fixture("before"), &diff_fixture("before")]);
+
+    assert_eq!(
+        stdout(&output),
+        "{\"added\":[],\"removed\":[],\"changed\":[]}\n"
+    );
+}
+
+#[test]
+fn diff_rejects_unreadable_setups() {
+    let missing = diff_fixture("missing");
+
+    assert!(!stderr(&monitor_utils(&["diff", &diff_fixture("before"), &missing])).is_empty());
+}
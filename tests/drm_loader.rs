@@ -0,0 +1,150 @@
+//! These tests run the DRM loader against a fake sysfs tree in `tests/fixtures/sysfs-drm`, which
+//! has a docked laptop panel, an external monitor, a disconnected port, a connected port which
+//! reports no modes and a connected monitor which is turned off.
+
+use monitor_utils::{
+    drm::DrmMonitorLoader, ConnectionState, LoadMonitors, Mode, MonitorSetup, OutputInventory,
+    Point, Rect,
+};
+
+fn fixture_loader() -> DrmMonitorLoader {
+    DrmMonitorLoader::in_directory(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/sysfs-drm"
+    ))
+    .unwrap()
+}
+
+#[test]
+fn missing_root_is_an_error() {
+    assert!(DrmMonitorLoader::in_directory("/nonexistent/sys/class/drm").is_err());
+}
+
+#[test]
+fn loads_connected_outputs_in_preferred_mode() {
+    let monitors = fixture_loader().load_monitors().unwrap();
+
+    let names = monitors.iter().map(|m| m.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["HDMI-A-1", "eDP-1"]);
+
+    assert_eq!(monitors[0].rect(), &Rect::new(2560, 1440, Point::new(0, 0)));
+    assert_eq!(
+        monitors[1].rect(),
+        &Rect::new(1920, 1200, Point::new(2560, 0))
+    );
+    assert_eq!(monitors[0].crtc(), 0);
+    assert_eq!(monitors[1].crtc(), 1);
+}
+
+#[test]
+fn disabled_outputs_are_skipped() {
+    let loader = fixture_loader();
+    let monitors = loader.load_monitors().unwrap();
+
+    // DP-3 reports modes, but is turned off
+    assert!(monitors.iter().all(|monitor| monitor.name() != "DP-3"));
+    assert!(loader
+        .connected_outputs()
+        .unwrap()
+        .iter()
+        .any(|output| output.name() == "DP-3"));
+}
+
+#[test]
+fn decodes_identities() {
+    let monitors = fixture_loader().load_monitors().unwrap();
+
+    let external = monitors[0].identity().unwrap();
+    assert_eq!(external.manufacturer(), "DEL");
+    assert_eq!(external.product_code(), 0xa0b8);
+    assert_eq!(external.serial_number(), 12345);
+    assert_eq!(external.product_name(), Some("DELL U2719D"));
+    assert_eq!(external.serial_string(), Some("ABC123"));
+    assert_eq!(external.to_string(), "DEL DELL U2719D (ABC123)");
+
+    let panel = monitors[1].identity().unwrap();
+    assert_eq!(panel.manufacturer(), "BOE");
+    assert_eq!(panel.product_name(), None);
+    assert_eq!(panel.to_string(), "BOE 0a1c");
+}
+
+#[test]
+fn connected_outputs_include_outputs_without_modes() {
+    let outputs = fixture_loader().connected_outputs().unwrap();
+
+    let names = outputs.iter().map(|o| o.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["DP-2", "DP-3", "HDMI-A-1", "eDP-1"]);
+    assert!(outputs[0].identity().is_none());
+}
+
+#[test]
+fn setup_from_fixture_is_valid() {
+    let setup = MonitorSetup::with_loader(fixture_loader()).unwrap();
+
+    assert!(!setup.validate().has_errors());
+    assert_eq!(
+        setup.monitor_by_name("eDP-1").unwrap().xinerama_index(),
+        Some(1)
+    );
+}
+
+#[test]
+fn inventory_lists_every_connector() {
+    let inventory = OutputInventory::with_loader(fixture_loader()).unwrap();
+
+    let states = inventory
+        .outputs()
+        .iter()
+        .map(|o| (o.name(), o.connection(), o.is_enabled()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        [
+            ("DP-1", ConnectionState::Disconnected, false),
+            ("DP-2", ConnectionState::Connected, false),
+            ("DP-3", ConnectionState::Connected, false),
+            ("HDMI-A-1", ConnectionState::Connected, true),
+            ("eDP-1", ConnectionState::Connected, true),
+        ]
+    );
+
+    let disabled = inventory.disabled().map(|o| o.name()).collect::<Vec<_>>();
+    assert_eq!(disabled, ["DP-2", "DP-3"]);
+    assert_eq!(
+        inventory.connected_outputs(),
+        fixture_loader().connected_outputs().unwrap()
+    );
+}
+
+#[test]
+fn reads_the_modes_of_each_display() {
+    let monitors = fixture_loader().load_monitors().unwrap();
+
+    // the external monitor lists 1920x1080 for several refresh rates, but it is read only once
+    let external = &monitors[0];
+    assert_eq!(
+        external.modes(),
+        [
+            Mode::new(2560, 1440, 0)
+                .with_preferred(true)
+                .with_current(true),
+            Mode::new(1920, 1080, 0),
+            Mode::new(1920, 1080, 0).with_interlaced(true),
+        ]
+    );
+    assert_eq!(external.preferred_mode(), external.current_mode());
+    assert_eq!(external.modes()[2].to_string(), "1920x1080i");
+
+    let inventory = OutputInventory::with_loader(fixture_loader()).unwrap();
+    let turned_off = inventory.output_by_name("DP-3").unwrap();
+    assert_eq!(
+        turned_off.preferred_mode(),
+        Some(&Mode::new(3840, 2160, 0).with_preferred(true))
+    );
+    assert!(turned_off.modes().iter().all(|mode| !mode.is_current()));
+    assert_eq!(
+        turned_off.identity().unwrap().product_name(),
+        Some("DELL U2720Q")
+    );
+    assert!(inventory.output_by_name("DP-2").unwrap().modes().is_empty());
+}
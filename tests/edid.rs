@@ -0,0 +1,120 @@
+use monitor_utils::{MonitorIdentity, MonitorUtilsError};
+
+const DESCRIPTOR_PRODUCT_NAME: u8 = 0xfc;
+const DESCRIPTOR_SERIAL_STRING: u8 = 0xff;
+
+/// Builds a base EDID block for a Dell display, with the given display descriptors in the last
+/// descriptor slots, and a valid checksum.
+fn edid(descriptors: &[(u8, &str)]) -> Vec<u8> {
+    let mut block = vec![0u8; 128];
+    block[..8].copy_from_slice(&[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+    // "DEL": D=4, E=5, L=12
+    block[8..10].copy_from_slice(&((4u16 << 10) | (5 << 5) | 12).to_be_bytes());
+    block[10..12].copy_from_slice(&0xa0f1u16.to_le_bytes());
+    block[12..16].copy_from_slice(&0x3134_4c42u32.to_le_bytes());
+
+    // a detailed timing descriptor, with a non-zero pixel clock, occupies the first slot
+    block[54..56].copy_from_slice(&[0x56, 0x5e]);
+    block[57] = DESCRIPTOR_PRODUCT_NAME;
+    block[59..64].copy_from_slice(b"bogus");
+
+    for (slot, (tag, text)) in [72, 90, 108].into_iter().zip(descriptors) {
+        block[slot + 3] = *tag;
+        let mut data = text.as_bytes().to_vec();
+        data.push(b'\n');
+        data.resize(13, b' ');
+        block[slot + 5..slot + 18].copy_from_slice(&data);
+    }
+
+    fix_checksum(&mut block);
+    block
+}
+
+fn fix_checksum(block: &mut [u8]) {
+    let sum = block[..127]
+        .iter()
+        .fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+    block[127] = sum.wrapping_neg();
+}
+
+fn invalid_reason(edid: &[u8]) -> String {
+    match MonitorIdentity::from_edid(edid) {
+        Err(MonitorUtilsError::InvalidEdid(reason)) => reason,
+        other => panic!("expected an invalid EDID, got {:?}", other),
+    }
+}
+
+#[test]
+fn decodes_the_vendor_block() {
+    let identity = MonitorIdentity::from_edid(&edid(&[])).unwrap();
+
+    assert_eq!(identity.manufacturer(), "DEL");
+    assert_eq!(identity.product_code(), 0xa0f1);
+    assert_eq!(identity.serial_number(), 0x3134_4c42);
+    // the timing descriptor is not mistaken for a product name
+    assert_eq!(identity.product_name(), None);
+    assert_eq!(identity.serial_string(), None);
+    assert_eq!(identity.to_string(), "DEL a0f1 (825510978)");
+}
+
+#[test]
+fn decodes_the_display_descriptors() {
+    let identity = MonitorIdentity::from_edid(&edid(&[
+        (0xfd, "range limits"),
+        (DESCRIPTOR_SERIAL_STRING, "7Y8KQ63"),
+        (DESCRIPTOR_PRODUCT_NAME, "DELL U2720Q"),
+    ]))
+    .unwrap();
+
+    assert_eq!(identity.product_name(), Some("DELL U2720Q"));
+    assert_eq!(identity.serial_string(), Some("7Y8KQ63"));
+    assert_eq!(identity.to_string(), "DEL DELL U2720Q (7Y8KQ63)");
+}
+
+#[test]
+fn extension_blocks_are_ignored() {
+    let base = edid(&[(DESCRIPTOR_PRODUCT_NAME, "DELL U2720Q")]);
+    let mut extended = base.clone();
+    extended.extend([0x02; 128]);
+
+    assert_eq!(
+        MonitorIdentity::from_edid(&extended).unwrap(),
+        MonitorIdentity::from_edid(&base).unwrap()
+    );
+}
+
+#[test]
+fn bad_checksum_is_rejected() {
+    let mut block = edid(&[]);
+    block[127] = block[127].wrapping_add(1);
+
+    assert_eq!(invalid_reason(&block), "checksum mismatch");
+}
+
+#[test]
+fn short_buffer_is_rejected() {
+    assert_eq!(
+        invalid_reason(&edid(&[])[..127]),
+        "shorter than a single block"
+    );
+    assert_eq!(invalid_reason(&[]), "shorter than a single block");
+}
+
+#[test]
+fn missing_header_is_rejected() {
+    let mut block = edid(&[]);
+    block[0] = 0xff;
+    fix_checksum(&mut block);
+
+    assert_eq!(invalid_reason(&block), "missing header");
+}
+
+#[test]
+fn malformed_manufacturer_is_rejected() {
+    let mut block = edid(&[]);
+    // a letter of 0 lies outside of 'A'..='Z'
+    block[8..10].copy_from_slice(&((4u16 << 10) | 12).to_be_bytes());
+    fix_checksum(&mut block);
+
+    assert_eq!(invalid_reason(&block), "malformed manufacturer ID");
+}
@@ -0,0 +1,104 @@
+//! These tests load the layouts in `tests/fixtures/layouts`, which describe a laptop with one
+//! monitor above it and a rotated monitor to the right.
+#![cfg(feature = "serialize")]
+
+use monitor_utils::{
+    file::FileMonitorLoader, LoadMonitors, MonitorSetup, Point, Rect, Reflection, Rotation, Scale,
+};
+
+fn fixture(name: &str) -> String {
+    format!(
+        "{}/tests/fixtures/layouts/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+fn assert_desk_layout(setup: &MonitorSetup) {
+    let laptop = setup.monitor_by_name("eDP-1").unwrap();
+    assert!(laptop.is_primary());
+    assert_eq!(laptop.rect(), &Rect::new(1920, 1080, Point::new(0, 1080)));
+    assert_eq!(laptop.xinerama_index(), Some(0));
+
+    let rotated = setup.monitor_by_name("HDMI-1").unwrap();
+    assert_eq!(rotated.rotation(), Rotation::Left);
+    assert_eq!(rotated.rect(), &Rect::new(1080, 1920, Point::new(1920, 0)));
+    assert_eq!(rotated.crtc(), 2);
+
+    let above = setup.monitor_above(laptop).unwrap();
+    assert_eq!(above.name(), "DP-1");
+    assert_eq!(setup.monitor_right_of(above).unwrap(), rotated);
+}
+
+#[test]
+fn loads_json_layout() {
+    let loader = FileMonitorLoader::from_path(fixture("desk.json"));
+    let setup = MonitorSetup::with_loader(loader).unwrap();
+
+    assert_desk_layout(&setup);
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn loads_toml_layout() {
+    let loader = FileMonitorLoader::from_path(fixture("desk.toml"));
+    let setup = MonitorSetup::with_loader(loader).unwrap();
+
+    assert_desk_layout(&setup);
+}
+
+#[test]
+fn optional_fields_have_defaults() {
+    let loader = FileMonitorLoader::from_contents(
+        r#"{ "monitors": [{ "name": "VGA-1", "width": 1024, "height": 768, "x": 0, "y": 0 }] }"#,
+    );
+    let monitors = loader.load_monitors().unwrap();
+
+    assert_eq!(monitors.len(), 1);
+    assert!(!monitors[0].is_primary());
+    assert_eq!(monitors[0].rotation(), Rotation::Normal);
+    assert_eq!(monitors[0].crtc(), 0);
+    assert_eq!(monitors[0].xinerama_index(), None);
+    assert_eq!(monitors[0].reflection(), Reflection::Normal);
+    assert_eq!(monitors[0].scale(), Scale::ONE);
+}
+
+#[test]
+fn reads_fractional_scale() {
+    let loader = FileMonitorLoader::from_contents(
+        r#"{ "monitors": [{ "name": "eDP-1", "width": 2048, "height": 1280, "x": 0, "y": 0, "scale": 1.25 }] }"#,
+    );
+    let monitors = loader.load_monitors().unwrap();
+
+    assert_eq!(monitors[0].scale(), Scale::from_120ths(150).unwrap());
+}
+
+#[test]
+fn invalid_layouts_are_errors() {
+    let missing_geometry = FileMonitorLoader::from_contents(r#"{ "monitors": [{ "name": "a" }] }"#);
+    assert!(missing_geometry.load_monitors().is_err());
+
+    let bad_rotation = FileMonitorLoader::from_contents(
+        r#"{ "monitors": [{ "name": "a", "width": 1, "height": 1, "x": 0, "y": 0, "rotation": "up" }] }"#,
+    );
+    assert_eq!(
+        bad_rotation.load_monitors().unwrap_err().to_string(),
+        "invalid rotation \"up\"; expected normal, left, inverted or right"
+    );
+
+    let bad_scale = FileMonitorLoader::from_contents(
+        r#"{ "monitors": [{ "name": "a", "width": 1, "height": 1, "x": 0, "y": 0, "scale": 0 }] }"#,
+    );
+    assert!(bad_scale.load_monitors().is_err());
+
+    let bad_reflection = FileMonitorLoader::from_contents(
+        r#"{ "monitors": [{ "name": "a", "width": 1, "height": 1, "x": 0, "y": 0, "reflection": "z" }] }"#,
+    );
+    assert_eq!(
+        bad_reflection.load_monitors().unwrap_err().to_string(),
+        "invalid reflection \"z\"; expected normal, x, y or xy"
+    );
+
+    let missing_file = FileMonitorLoader::from_path(fixture("nonexistent.json"));
+    assert!(missing_file.load_monitors().is_err());
+}
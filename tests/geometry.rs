@@ -0,0 +1,91 @@
+use monitor_utils::{MonitorUtilsError, Point, Rect};
+
+/// Yields the reason given for rejecting the geometry.
+fn rejection(geometry: &str) -> String {
+    match geometry.parse::<Rect>() {
+        Err(MonitorUtilsError::InvalidGeometry {
+            geometry: input,
+            reason,
+        }) => {
+            assert_eq!(input, geometry);
+            reason
+        }
+        other => panic!("expected {:?} to be rejected, got {:?}", geometry, other),
+    }
+}
+
+#[test]
+fn parses_xrandr_geometries() {
+    assert_eq!(
+        "1920x1080+0+0".parse::<Rect>().unwrap(),
+        Rect::new(1920, 1080, Point::new(0, 0))
+    );
+    assert_eq!(
+        "1080x1920+2560+360".parse::<Rect>().unwrap(),
+        Rect::new(1080, 1920, Point::new(2560, 360))
+    );
+    assert_eq!(
+        "4294967295x1+4294967295+0".parse::<Rect>().unwrap(),
+        Rect::new(u32::MAX, 1, Point::new(u32::MAX, 0))
+    );
+}
+
+#[test]
+fn missing_parts_are_rejected() {
+    assert_eq!(rejection("1920x1080"), "missing offset");
+    assert_eq!(rejection("1920x1080+0"), "missing y offset");
+    assert_eq!(
+        rejection("1920+0+0"),
+        "missing 'x' between width and height"
+    );
+    assert_eq!(rejection(""), "missing offset");
+    assert_eq!(rejection("x1080+0+0"), "width \"\" is not a number");
+    assert_eq!(rejection("1920x1080+0+"), "y offset \"\" is not a number");
+}
+
+#[test]
+fn non_numeric_parts_are_rejected() {
+    assert_eq!(
+        rejection("wide x1080+0+0"),
+        "width \"wide \" is not a number"
+    );
+    assert_eq!(
+        rejection("1920x1080.5+0+0"),
+        "height \"1080.5\" is not a number"
+    );
+    assert_eq!(
+        rejection("1920x1080+-1+0"),
+        "x offset \"-1\" is not a number"
+    );
+    assert_eq!(
+        rejection("1920x1080+0+0+0"),
+        "y offset \"0+0\" is not a number"
+    );
+}
+
+#[test]
+fn leading_plus_is_rejected() {
+    // `u32::from_str` would accept "+1", which would make these valid
+    assert_eq!(rejection("1x1+0++1"), "y offset \"+1\" is not a number");
+    assert_eq!(rejection("1x1++1+1"), "x offset \"\" is not a number");
+}
+
+#[test]
+fn overflow_is_rejected() {
+    assert_eq!(
+        rejection("4294967296x1+0+0"),
+        "width 4294967296 is too large"
+    );
+    assert_eq!(
+        rejection("1x1+0+99999999999999999999"),
+        "y offset 99999999999999999999 is too large"
+    );
+}
+
+#[test]
+fn errors_name_the_geometry() {
+    assert_eq!(
+        "1920x1080".parse::<Rect>().unwrap_err().to_string(),
+        "invalid geometry \"1920x1080\": missing offset; expected WxH+X+Y"
+    );
+}
@@ -0,0 +1,180 @@
+use monitor_utils::{InputMatrix, Monitor, MonitorSetup, Point, Rect, Reflection, Rotation};
+
+fn assert_close(actual: (f64, f64), expected: (f64, f64)) {
+    assert!(
+        (actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9,
+        "{:?} != {:?}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn single_monitor_covers_the_whole_screen() {
+    let setup = MonitorSetup::new(vec![Monitor::new(
+        "eDP-1".to_owned(),
+        0,
+        Rect::new(1920, 1080, Point::new(0, 0)),
+    )]);
+    let monitor = &setup.monitors()[0];
+
+    assert_eq!(setup.screen_size(), (1920, 1080));
+    assert_eq!(setup.input_matrix(monitor), InputMatrix::IDENTITY);
+    assert_eq!(InputMatrix::IDENTITY.to_string(), "1 0 0 0 1 0 0 0 1");
+}
+
+#[test]
+fn matrix_restricts_the_device_to_the_monitor() {
+    let setup = MonitorSetup::new(vec![
+        Monitor::new(
+            "eDP-1".to_owned(),
+            0,
+            Rect::new(1920, 1080, Point::new(0, 1080)),
+        ),
+        Monitor::new(
+            "DP-1".to_owned(),
+            1,
+            Rect::new(1920, 2160, Point::new(1920, 0)),
+        ),
+    ]);
+
+    assert_eq!(setup.screen_size(), (3840, 2160));
+
+    let laptop = setup.monitor_by_name("eDP-1").unwrap();
+    assert_eq!(
+        setup.input_matrix(laptop).to_string(),
+        "0.5 0 0 0 0.5 0.5 0 0 1"
+    );
+
+    let external = setup.monitor_by_name("DP-1").unwrap();
+    assert_eq!(
+        setup.input_matrix(external).to_string(),
+        "0.5 0 0.5 0 1 0 0 0 1"
+    );
+}
+
+#[test]
+fn rotated_monitors_yield_the_usual_matrices() {
+    let expected = [
+        (Rotation::Normal, "1 0 0 0 1 0 0 0 1"),
+        (Rotation::Left, "0 -1 1 1 0 0 0 0 1"),
+        (Rotation::Inverted, "-1 0 1 0 -1 1 0 0 1"),
+        (Rotation::Right, "0 1 0 -1 0 1 0 0 1"),
+    ];
+
+    for (rotation, matrix) in expected {
+        let setup = MonitorSetup::new(vec![Monitor::new(
+            "eDP-1".to_owned(),
+            0,
+            Rect::new(1080, 1920, Point::new(0, 0)),
+        )
+        .with_rotation(rotation)]);
+
+        assert_eq!(
+            setup.input_matrix(&setup.monitors()[0]).to_string(),
+            matrix,
+            "{:?}",
+            rotation
+        );
+    }
+}
+
+#[test]
+fn matrix_agrees_with_panel_coords() {
+    let rotations = [
+        Rotation::Normal,
+        Rotation::Left,
+        Rotation::Inverted,
+        Rotation::Right,
+    ];
+    let reflections = [
+        Reflection::Normal,
+        Reflection::X,
+        Reflection::Y,
+        Reflection::XY,
+    ];
+
+    for rotation in rotations {
+        for reflection in reflections {
+            let setup = MonitorSetup::new(vec![
+                Monitor::new("eDP-1".to_owned(), 0, Rect::new(400, 300, Point::new(0, 0))),
+                Monitor::new(
+                    "DP-1".to_owned(),
+                    1,
+                    Rect::new(300, 400, Point::new(400, 0)),
+                )
+                .with_rotation(rotation)
+                .with_reflection(reflection),
+            ]);
+            let monitor = setup.monitor_by_name("DP-1").unwrap();
+            let matrix = setup.input_matrix(monitor);
+            let (panel_width, panel_height) = monitor.panel_size();
+
+            // the centers of the corner pixels of the panel
+            for (x, y) in [
+                (0, 0),
+                (panel_width - 1, 0),
+                (0, panel_height - 1),
+                (panel_width - 1, panel_height - 1),
+            ] {
+                let point = monitor.from_panel_coords(&Point::new(x, y)).unwrap();
+                let device = (
+                    (x as f64 + 0.5) / panel_width as f64,
+                    (y as f64 + 0.5) / panel_height as f64,
+                );
+                let screen = (
+                    (point.x() as f64 + 0.5) / 700.0,
+                    (point.y() as f64 + 0.5) / 400.0,
+                );
+
+                assert_close(matrix.transform(device.0, device.1), screen);
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "x11")]
+fn xinput_args_set_the_matrix() {
+    let matrix = InputMatrix::new([0.5, 0.0, 0.5, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+
+    assert_eq!(
+        matrix.to_xinput_args("Wacom Intuos S Pen stylus"),
+        [
+            "set-prop",
+            "Wacom Intuos S Pen stylus",
+            "Coordinate Transformation Matrix",
+            "0.5",
+            "0",
+            "0.5",
+            "0",
+            "1",
+            "0",
+            "0",
+            "0",
+            "1"
+        ]
+    );
+}
+
+#[test]
+fn screen_size_is_clipped_to_the_coordinate_space() {
+    let setup = MonitorSetup::new(vec![
+        Monitor::new(
+            "DP-1".to_owned(),
+            0,
+            Rect::new(1920, 1080, Point::new(0, 0)),
+        ),
+        Monitor::new(
+            "DP-2".to_owned(),
+            1,
+            Rect::new(1920, 1080, Point::new(u32::MAX - 100, u32::MAX - 100)),
+        ),
+    ]);
+
+    assert_eq!(setup.screen_size(), (u32::MAX, u32::MAX));
+    assert!(setup
+        .input_matrix(setup.monitor_by_name("DP-1").unwrap())
+        .to_string()
+        .starts_with("0.0000004470348"));
+}
@@ -0,0 +1,171 @@
+use monitor_utils::{MapMode, Monitor, MonitorSetup, Point, Rect};
+
+fn rect(width: u32, height: u32, x: u32, y: u32) -> Rect {
+    Rect::new(width, height, Point::new(x, y))
+}
+
+/// A 1080p monitor next to a larger 1440p monitor.
+fn setup() -> MonitorSetup {
+    MonitorSetup::new(vec![
+        Monitor::new("DP-1".to_owned(), 0, rect(1920, 1080, 0, 0)),
+        Monitor::new("DP-2".to_owned(), 1, rect(2560, 1440, 1920, 0)),
+    ])
+}
+
+#[test]
+fn map_point_keeps_the_relative_position() {
+    let setup = setup();
+    let small = setup.monitor_by_name("DP-1").unwrap();
+    let large = setup.monitor_by_name("DP-2").unwrap();
+
+    assert_eq!(
+        setup.map_point(&Point::new(0, 0), small, large),
+        Point::new(1920, 0)
+    );
+    assert_eq!(
+        setup.map_point(&small.rect().center(), small, large),
+        large.rect().center()
+    );
+    assert_eq!(
+        setup.map_point(&Point::new(1920 + 640, 360), large, small),
+        Point::new(480, 270)
+    );
+}
+
+#[test]
+fn preserve_pixels_keeps_size_and_offset() {
+    let setup = setup();
+    let small = setup.monitor_by_name("DP-1").unwrap();
+    let large = setup.monitor_by_name("DP-2").unwrap();
+
+    let window = rect(800, 600, 100, 50);
+    let mapped = setup.map_rect(&window, small, large, MapMode::PreservePixels);
+    assert_eq!(mapped, rect(800, 600, 2020, 50));
+
+    // and back again
+    assert_eq!(
+        setup.map_rect(&mapped, large, small, MapMode::PreservePixels),
+        window
+    );
+}
+
+#[test]
+fn preserve_pixels_clamps_at_the_edges() {
+    let setup = setup();
+    let small = setup.monitor_by_name("DP-1").unwrap();
+    let large = setup.monitor_by_name("DP-2").unwrap();
+
+    // too large for the smaller monitor, so it is shrunk along x and shifted up along y
+    let window = rect(2000, 1000, 1920 + 500, 400);
+    assert_eq!(
+        setup.map_rect(&window, large, small, MapMode::PreservePixels),
+        rect(1920, 1000, 0, 80)
+    );
+
+    // hanging off the left edge of its monitor, so it is shifted onto the target
+    let window = rect(400, 300, 1820, 100);
+    assert_eq!(
+        setup.map_rect(&window, large, small, MapMode::PreservePixels),
+        rect(400, 300, 0, 100)
+    );
+
+    // hanging off the bottom-right corner of its monitor
+    let window = rect(400, 300, 1700, 900);
+    assert_eq!(
+        setup.map_rect(&window, small, large, MapMode::PreservePixels),
+        rect(400, 300, 1920 + 1700, 900)
+    );
+    assert_eq!(
+        setup.map_rect(
+            &rect(400, 300, 1920 + 2400, 1300),
+            large,
+            small,
+            MapMode::PreservePixels
+        ),
+        rect(400, 300, 1520, 780)
+    );
+}
+
+#[test]
+fn preserve_relative_scales_with_the_monitor() {
+    let setup = setup();
+    let small = setup.monitor_by_name("DP-1").unwrap();
+    let large = setup.monitor_by_name("DP-2").unwrap();
+
+    let window = rect(960, 540, 480, 270);
+    let mapped = setup.map_rect(&window, small, large, MapMode::PreserveRelative);
+    assert_eq!(mapped, rect(1280, 720, 1920 + 640, 360));
+    assert_eq!(
+        setup.map_rect(&mapped, large, small, MapMode::PreserveRelative),
+        window
+    );
+}
+
+#[test]
+fn preserve_maximized_spans_the_target_along_spanned_axes() {
+    let setup = setup();
+    let small = setup.monitor_by_name("DP-1").unwrap();
+    let large = setup.monitor_by_name("DP-2").unwrap();
+
+    // fully maximized
+    assert_eq!(
+        setup.map_rect(small.rect(), small, large, MapMode::PreserveMaximized),
+        *large.rect()
+    );
+
+    // maximized horizontally only, below a 40 pixel panel
+    assert_eq!(
+        setup.map_rect(
+            &rect(1920, 1040, 0, 40),
+            small,
+            large,
+            MapMode::PreserveMaximized
+        ),
+        rect(2560, 1040, 1920, 40)
+    );
+
+    // tiled to the left half, spanning the monitor vertically
+    assert_eq!(
+        setup.map_rect(
+            &rect(1280, 1440, 1920, 0),
+            large,
+            small,
+            MapMode::PreserveMaximized
+        ),
+        rect(1280, 1080, 0, 0)
+    );
+
+    // not spanning either axis, which behaves like `PreservePixels`
+    let window = rect(800, 600, 100, 50);
+    assert_eq!(
+        setup.map_rect(&window, small, large, MapMode::PreserveMaximized),
+        setup.map_rect(&window, small, large, MapMode::PreservePixels)
+    );
+}
+
+#[test]
+fn rects_at_the_end_of_the_coordinate_space_map() {
+    // the far monitor reaches past the end of the coordinate space
+    let setup = MonitorSetup::new(vec![
+        Monitor::new("DP-1".to_owned(), 0, rect(10, 10, u32::MAX - 5, 0)),
+        Monitor::new("DP-2".to_owned(), 1, rect(10, 10, 0, 0)),
+    ]);
+    let far = setup.monitor_by_name("DP-1").unwrap();
+    let near = setup.monitor_by_name("DP-2").unwrap();
+
+    let window = rect(5, 5, u32::MAX - 5, 0);
+    for mode in [
+        MapMode::PreservePixels,
+        MapMode::PreserveRelative,
+        MapMode::PreserveMaximized,
+    ] {
+        assert_eq!(
+            setup.map_rect(&window, far, near, mode).offset(),
+            Point::new(0, 0)
+        );
+        assert_eq!(
+            setup.map_rect(&rect(5, 5, 0, 0), near, far, mode).offset(),
+            window.offset()
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use monitor_utils::{Monitor, MonitorSetup, Point, Rect};
+
+fn monitor(name: &str, x: u32) -> Monitor {
+    Monitor::new(name.to_owned(), 0, Rect::new(1920, 1080, Point::new(x, 0)))
+}
+
+/// A laptop panel cloned onto a projector, next to an external monitor.
+fn presentation_setup() -> MonitorSetup {
+    MonitorSetup::new(vec![
+        monitor("eDP-1", 0).with_xinerama_index(1),
+        monitor("DP-1", 1920).with_xinerama_index(2),
+        monitor("HDMI-1", 0)
+            .with_primary(true)
+            .with_xinerama_index(0),
+    ])
+}
+
+#[test]
+fn identical_rects_are_grouped_into_the_first_monitor() {
+    let setup = presentation_setup();
+
+    assert_eq!(setup.monitors().len(), 2);
+
+    let group = setup.monitor_by_name("eDP-1").unwrap();
+    assert_eq!(group.name(), "eDP-1");
+    assert_eq!(group.mirrors(), ["HDMI-1"]);
+    assert!(group.is_mirrored());
+    assert_eq!(group.members().collect::<Vec<_>>(), ["eDP-1", "HDMI-1"]);
+
+    // the group takes on the attributes of its members
+    assert!(group.is_primary());
+    assert_eq!(group.xinerama_index(), Some(0));
+
+    let external = setup.monitor_by_name("DP-1").unwrap();
+    assert!(!external.is_mirrored());
+    assert_eq!(external.members().collect::<Vec<_>>(), ["DP-1"]);
+}
+
+#[test]
+fn mirrors_resolve_to_their_group() {
+    let setup = presentation_setup();
+    let group = setup.monitor_by_name("eDP-1").unwrap();
+
+    assert_eq!(setup.monitor_by_name("HDMI-1").unwrap(), group);
+    assert_eq!(
+        setup
+            .monitor_containing_point(&Point::new(100, 100))
+            .unwrap(),
+        group
+    );
+
+    // traversals never visit the mirrored region twice
+    let external = setup.next_monitor_clockwise(group).unwrap();
+    assert_eq!(external.name(), "DP-1");
+    assert_eq!(setup.next_monitor_clockwise(external).unwrap(), group);
+}
+
+#[test]
+fn regrouping_keeps_every_member() {
+    let setup = presentation_setup();
+
+    // feeding grouped monitors back in, e.g. from a cache, merges whole groups
+    let mut monitors = setup.monitors().to_vec();
+    monitors.push(monitor("DP-2", 0));
+    let regrouped = MonitorSetup::new(monitors);
+
+    let group = regrouped.monitor_by_name("DP-2").unwrap();
+    assert_eq!(
+        group.members().collect::<Vec<_>>(),
+        ["eDP-1", "HDMI-1", "DP-2"]
+    );
+}
@@ -0,0 +1,82 @@
+//! These tests cover the presets and failure injection of the `testing` feature.
+#![cfg(feature = "testing")]
+
+use monitor_utils::testing::MockMonitorLoader;
+use monitor_utils::{LoadMonitors, Monitor, MonitorSetup, Point, Rect};
+
+#[test]
+fn presets_have_valid_layouts() {
+    let presets = [
+        MockMonitorLoader::single_1080p(),
+        MockMonitorLoader::dual_side_by_side(),
+        MockMonitorLoader::triple_side_by_side(),
+        MockMonitorLoader::laptop_plus_vertical(),
+        MockMonitorLoader::laptop_below_external(),
+        MockMonitorLoader::mirrored_projector(),
+    ];
+
+    for preset in presets {
+        let setup = preset.to_setup();
+        let primaries = setup.monitors().iter().filter(|m| m.is_primary()).count();
+
+        assert!(!setup.validate().has_errors(), "{:?}", setup);
+        assert_eq!(primaries, 1, "{:?}", setup);
+    }
+}
+
+#[test]
+fn presets_are_laid_out_as_described() {
+    let setup = MockMonitorLoader::laptop_plus_vertical().to_setup();
+    let laptop = setup.monitor_by_name("eDP-1").unwrap();
+    assert_eq!(setup.monitor_right_of(laptop).unwrap().name(), "HDMI-1");
+
+    let setup = MockMonitorLoader::laptop_below_external().to_setup();
+    let laptop = setup.monitor_by_name("eDP-1").unwrap();
+    assert_eq!(setup.monitor_above(laptop).unwrap().name(), "DP-1");
+
+    let setup = MockMonitorLoader::mirrored_projector().to_setup();
+    assert_eq!(setup.monitors().len(), 1);
+    assert_eq!(setup.monitors()[0].mirrors(), ["HDMI-1"]);
+}
+
+#[test]
+fn calls_are_counted() {
+    let loader = MockMonitorLoader::single_1080p();
+    assert_eq!(loader.call_count(), 0);
+
+    let mut setup = MonitorSetup::with_loader(&loader).unwrap();
+    setup.reload(&loader).unwrap();
+
+    assert_eq!(loader.call_count(), 2);
+}
+
+#[test]
+fn failures_are_injected() {
+    let loader = MockMonitorLoader::dual_side_by_side().fail_on_call(2, "unplugged");
+
+    assert!(loader.load_monitors().is_ok());
+    assert_eq!(loader.load_monitors().unwrap_err().message(), "unplugged");
+    assert!(loader.load_monitors().is_ok());
+
+    let loader = MockMonitorLoader::single_1080p().fail_always("no display");
+    assert!(MonitorSetup::with_loader(&loader).is_err());
+    assert!(loader.load_monitors().is_err());
+    assert_eq!(loader.call_count(), 2);
+}
+
+#[test]
+fn monitors_can_be_replaced_between_reloads() {
+    let loader = MockMonitorLoader::single_1080p();
+    let mut setup = MonitorSetup::with_loader(&loader).unwrap();
+
+    let mut monitors = loader.monitors();
+    monitors.push(Monitor::new(
+        "HDMI-1".to_owned(),
+        1,
+        Rect::new(1920, 1080, Point::new(1920, 0)),
+    ));
+    loader.set_monitors(monitors);
+    setup.reload(&loader).unwrap();
+
+    assert_eq!(setup.monitors().len(), 2);
+}
@@ -0,0 +1,231 @@
+//! These tests cover every combination of rotation and reflection on a panel which is 4 pixels
+//! wide and 3 pixels high before rotation, so that sideways rotations can be told apart.
+
+use monitor_utils::{Monitor, Point, Rect, Reflection, Rotation, Scale};
+
+const ROTATIONS: [Rotation; 4] = [
+    Rotation::Normal,
+    Rotation::Left,
+    Rotation::Inverted,
+    Rotation::Right,
+];
+
+const REFLECTIONS: [Reflection; 4] = [
+    Reflection::Normal,
+    Reflection::X,
+    Reflection::Y,
+    Reflection::XY,
+];
+
+/// The top-left corner of the monitor within the virtual screen.
+fn offset() -> Point {
+    Point::new(100, 50)
+}
+
+fn monitor(rotation: Rotation, reflection: Reflection) -> Monitor {
+    let rect = if rotation.is_sideways() {
+        Rect::new(3, 4, offset())
+    } else {
+        Rect::new(4, 3, offset())
+    };
+
+    Monitor::new("DP-1".to_owned(), 0, rect)
+        .with_rotation(rotation)
+        .with_reflection(reflection)
+}
+
+/// Yields the point of the virtual screen displaying the given pixel of the panel, relative to the
+/// top-left corner of the monitor.
+fn displayed_at(monitor: &Monitor, x: u32, y: u32) -> (u32, u32) {
+    let point = monitor.from_panel_coords(&Point::new(x, y)).unwrap();
+    (point.x() - offset().x(), point.y() - offset().y())
+}
+
+#[test]
+fn panel_size_is_before_rotation() {
+    for rotation in ROTATIONS {
+        for reflection in REFLECTIONS {
+            assert_eq!(monitor(rotation, reflection).panel_size(), (4, 3));
+        }
+    }
+}
+
+#[test]
+fn every_combination_places_the_panel_as_expected() {
+    // where the top-left pixel of the panel and its right neighbour are displayed, i.e. where the
+    // panel starts and in which direction its rows run
+    let expected = [
+        (Rotation::Normal, Reflection::Normal, (0, 0), (1, 0)),
+        (Rotation::Left, Reflection::Normal, (2, 0), (2, 1)),
+        (Rotation::Inverted, Reflection::Normal, (3, 2), (2, 2)),
+        (Rotation::Right, Reflection::Normal, (0, 3), (0, 2)),
+        (Rotation::Normal, Reflection::X, (3, 0), (2, 0)),
+        (Rotation::Left, Reflection::X, (0, 0), (0, 1)),
+        (Rotation::Inverted, Reflection::X, (0, 2), (1, 2)),
+        (Rotation::Right, Reflection::X, (2, 3), (2, 2)),
+    ];
+
+    for (rotation, reflection, origin, next) in expected {
+        let monitor = monitor(rotation, reflection);
+
+        assert_eq!(
+            displayed_at(&monitor, 0, 0),
+            origin,
+            "{:?} {:?}",
+            rotation,
+            reflection
+        );
+        assert_eq!(
+            displayed_at(&monitor, 1, 0),
+            next,
+            "{:?} {:?}",
+            rotation,
+            reflection
+        );
+    }
+}
+
+#[test]
+fn reflections_across_y_and_both_axes_equal_other_combinations() {
+    let half_turn = |rotation| match rotation {
+        Rotation::Normal => Rotation::Inverted,
+        Rotation::Left => Rotation::Right,
+        Rotation::Inverted => Rotation::Normal,
+        Rotation::Right => Rotation::Left,
+    };
+
+    for rotation in ROTATIONS {
+        let pairs = [
+            (
+                monitor(rotation, Reflection::Y),
+                monitor(half_turn(rotation), Reflection::X),
+            ),
+            (
+                monitor(rotation, Reflection::XY),
+                monitor(half_turn(rotation), Reflection::Normal),
+            ),
+        ];
+
+        for (reflected, equivalent) in pairs {
+            for x in 0..4 {
+                for y in 0..3 {
+                    assert_eq!(
+                        displayed_at(&reflected, x, y),
+                        displayed_at(&equivalent, x, y)
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn panel_coords_round_trip_for_every_combination() {
+    for rotation in ROTATIONS {
+        for reflection in REFLECTIONS {
+            let monitor = monitor(rotation, reflection);
+            let rect = monitor.rect();
+            let mut panel_points = Vec::new();
+
+            for x in rect.offset().x()..rect.offset().x() + rect.width() {
+                for y in rect.offset().y()..rect.offset().y() + rect.height() {
+                    let point = Point::new(x, y);
+                    let panel = monitor.to_panel_coords(&point).unwrap();
+
+                    assert!(panel.x() < 4 && panel.y() < 3);
+                    assert_eq!(monitor.from_panel_coords(&panel).unwrap(), point);
+                    panel_points.push((panel.x(), panel.y()));
+                }
+            }
+
+            // every pixel of the panel displays exactly one point
+            panel_points.sort();
+            panel_points.dedup();
+            assert_eq!(panel_points.len(), 12, "{:?} {:?}", rotation, reflection);
+        }
+    }
+}
+
+#[test]
+fn points_outside_the_monitor_or_panel_are_errors() {
+    let monitor = monitor(Rotation::Left, Reflection::X);
+
+    assert!(monitor.to_panel_coords(&Point::new(0, 0)).is_err());
+    assert!(monitor.to_panel_coords(&Point::new(103, 50)).is_err());
+    assert!(monitor.from_panel_coords(&Point::new(4, 0)).is_err());
+    assert!(monitor.from_panel_coords(&Point::new(0, 3)).is_err());
+}
+
+#[test]
+fn panel_coords_are_physical_pixels() {
+    let monitor = Monitor::new("eDP-1".to_owned(), 0, Rect::new(2, 3, Point::new(0, 0)))
+        .with_rotation(Rotation::Left)
+        .with_scale(Scale::from_120ths(240).unwrap());
+
+    assert_eq!(monitor.panel_size(), (6, 4));
+    assert_eq!(
+        monitor.to_panel_coords(&Point::new(0, 0)).unwrap(),
+        Point::new(0, 3)
+    );
+    assert_eq!(
+        monitor.from_panel_coords(&Point::new(5, 0)).unwrap(),
+        Point::new(1, 2)
+    );
+}
+
+#[test]
+fn names_parse_back() {
+    for rotation in ROTATIONS {
+        assert_eq!(rotation.name().parse::<Rotation>().unwrap(), rotation);
+    }
+    for reflection in REFLECTIONS {
+        assert_eq!(reflection.name().parse::<Reflection>().unwrap(), reflection);
+    }
+
+    // as printed by `xrandr`
+    assert_eq!(
+        "X and Y axis".parse::<Reflection>().unwrap(),
+        Reflection::XY
+    );
+
+    assert!("up".parse::<Rotation>().is_err());
+    assert!("Left".parse::<Rotation>().is_err());
+    assert!("z".parse::<Reflection>().is_err());
+}
+
+#[test]
+fn downscaled_panel_coords_stay_on_the_panel() {
+    // a 1920x1080 mode scaled by `xrandr --scale 2x2`, i.e. by a factor of 0.5
+    for rotation in ROTATIONS {
+        for reflection in REFLECTIONS {
+            let rect = if rotation.is_sideways() {
+                Rect::new(2160, 3840, Point::new(0, 0))
+            } else {
+                Rect::new(3840, 2160, Point::new(0, 0))
+            };
+            let monitor = Monitor::new("DP-1".to_owned(), 0, rect.clone())
+                .with_rotation(rotation)
+                .with_reflection(reflection)
+                .with_scale(Scale::from_120ths(60).unwrap());
+            assert_eq!(monitor.panel_size(), (1920, 1080));
+
+            let far_corner = Point::new(rect.width() - 1, rect.height() - 1);
+            for point in [Point::new(0, 0), far_corner] {
+                let panel = monitor.to_panel_coords(&point).unwrap();
+                assert!(panel.x() < 1920 && panel.y() < 1080, "{:?}", panel);
+            }
+        }
+    }
+
+    let monitor = Monitor::new(
+        "DP-1".to_owned(),
+        0,
+        Rect::new(2160, 3840, Point::new(0, 0)),
+    )
+    .with_rotation(Rotation::Left)
+    .with_scale(Scale::from_120ths(60).unwrap());
+    assert_eq!(
+        monitor.to_panel_coords(&Point::new(2159, 3839)).unwrap(),
+        Point::new(1919, 0)
+    );
+}
@@ -0,0 +1,172 @@
+#![cfg(feature = "global-cache")]
+
+use monitor_utils::{
+    ConnectedOutput, Monitor, MonitorSetup, Point, Profiles, Rect, Reflection, Rotation, Scale,
+};
+
+/// Yields profiles stored in a fresh directory, unique to the test.
+fn profiles(test: &str) -> Profiles {
+    let directory = std::env::temp_dir().join(format!(
+        "monitor-utils-profiles-{}-{}",
+        std::process::id(),
+        test
+    ));
+    let _ = std::fs::remove_dir_all(&directory);
+
+    Profiles::in_directory(directory)
+}
+
+/// Stores the JSON as the given profile, as if it had been saved by an earlier version.
+fn write_profile(profiles: &Profiles, name: &str, json: &str) {
+    std::fs::create_dir_all(profiles.directory()).unwrap();
+    std::fs::write(profiles.directory().join(format!("{}.json", name)), json).unwrap();
+}
+
+fn monitor(name: &str, x: u32) -> Monitor {
+    Monitor::new(name.to_owned(), 0, Rect::new(1920, 1080, Point::new(x, 0)))
+}
+
+fn connected(names: &[&str]) -> Vec<ConnectedOutput> {
+    names
+        .iter()
+        .map(|name| ConnectedOutput::new(name.to_string(), None))
+        .collect()
+}
+
+#[test]
+fn lid_closed_profile_ignores_the_disabled_panel() {
+    let profiles = profiles("lid-closed");
+    // saved with the lid closed: the panel is connected, but not part of the layout
+    let docked = MonitorSetup::new(vec![monitor("DP-1", 0), monitor("DP-2", 1920)]);
+    profiles.save("docked", &docked).unwrap();
+
+    let outputs = connected(&["eDP-1", "DP-1", "DP-2"]);
+    assert_eq!(
+        profiles.detect(&outputs).unwrap().as_deref(),
+        Some("docked")
+    );
+
+    let setup = profiles.load_for_outputs("docked", &outputs).unwrap();
+    assert_eq!(setup.monitors().len(), 2);
+    assert!(setup.monitor_by_name("eDP-1").is_none());
+}
+
+#[test]
+fn missing_outputs_never_match() {
+    let profiles = profiles("missing");
+    let docked = MonitorSetup::new(vec![monitor("DP-1", 0), monitor("DP-2", 1920)]);
+    profiles.save("docked", &docked).unwrap();
+
+    assert_eq!(
+        profiles.detect(&connected(&["eDP-1", "DP-1"])).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn profile_using_the_most_outputs_wins() {
+    let profiles = profiles("most-outputs");
+    profiles
+        .save(
+            "desk",
+            &MonitorSetup::new(vec![monitor("eDP-1", 0), monitor("DP-1", 1920)]),
+        )
+        .unwrap();
+    profiles
+        .save("laptop", &MonitorSetup::new(vec![monitor("eDP-1", 0)]))
+        .unwrap();
+
+    assert_eq!(
+        profiles
+            .detect(&connected(&["eDP-1", "DP-1"]))
+            .unwrap()
+            .as_deref(),
+        Some("desk")
+    );
+    assert_eq!(
+        profiles.detect(&connected(&["eDP-1"])).unwrap().as_deref(),
+        Some("laptop")
+    );
+}
+
+#[test]
+fn profiles_saved_without_modes_load() {
+    let profiles = profiles("without-modes");
+    write_profile(
+        &profiles,
+        "desk",
+        r#"{"monitors":[{"order":0,"name":"DP-1","crtc":0,"primary":true,"xinerama_index":0,
+            "rotation":"Normal","reflection":"X","identity":null,"scale":{"numerator":150},
+            "rect":{"width":1536,"height":864,"offset":{"x":0,"y":0}},"mirrors":[]}]}"#,
+    );
+
+    let setup = profiles.load("desk").unwrap();
+    let monitor = setup.monitor_by_name("DP-1").unwrap();
+    assert!(monitor.modes().is_empty());
+    assert_eq!(monitor.preferred_mode(), None);
+    assert_eq!(monitor.reflection(), Reflection::X);
+    assert_eq!(monitor.scale(), Scale::from_120ths(150).unwrap());
+}
+
+#[test]
+fn profiles_saved_without_scales_load() {
+    let profiles = profiles("without-scales");
+    write_profile(
+        &profiles,
+        "desk",
+        r#"{"monitors":[{"order":0,"name":"DP-1","crtc":0,"primary":true,"xinerama_index":0,
+            "rotation":"Left","reflection":"Y","identity":null,
+            "rect":{"width":1080,"height":1920,"offset":{"x":0,"y":0}},"mirrors":[]}]}"#,
+    );
+
+    let setup = profiles.load("desk").unwrap();
+    let monitor = setup.monitor_by_name("DP-1").unwrap();
+    assert_eq!(monitor.scale(), Scale::ONE);
+    assert_eq!(setup.physical_rect(monitor), *monitor.rect());
+    assert_eq!(monitor.reflection(), Reflection::Y);
+
+    // a scale of 1 is stored in the same way, so the monitors compare equal
+    assert_eq!(*monitor, monitor.clone().with_scale(Scale::ONE));
+}
+
+/// `tests/fixtures/profiles/desk.json` was saved before modes, scales and reflections were
+/// recorded: a laptop panel mirrored onto a projector, below a monitor and next to a rotated one.
+#[test]
+fn profiles_saved_by_earlier_versions_load() {
+    let profiles = Profiles::in_directory(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/profiles"
+    ));
+    let setup = profiles.load("desk").unwrap();
+
+    for monitor in setup.monitors() {
+        assert!(monitor.modes().is_empty());
+        assert_eq!(monitor.scale(), Scale::ONE);
+        assert_eq!(monitor.reflection(), Reflection::Normal);
+    }
+
+    let laptop = setup.monitor_by_name("HDMI-2").unwrap();
+    assert_eq!(laptop.name(), "eDP-1");
+    assert!(laptop.is_primary());
+    assert_eq!(laptop.identity().unwrap().manufacturer(), "BOE");
+    assert_eq!(
+        setup.monitor_by_name("HDMI-1").unwrap().rotation(),
+        Rotation::Left
+    );
+
+    // the monitor above the laptop is now plugged into another port
+    let dell = setup.monitor_by_name("DP-1").unwrap().identity().cloned();
+    let outputs = [
+        ConnectedOutput::new("eDP-1".to_owned(), laptop.identity().cloned()),
+        ConnectedOutput::new("HDMI-1".to_owned(), None),
+        ConnectedOutput::new("HDMI-2".to_owned(), None),
+        ConnectedOutput::new("DP-2".to_owned(), dell),
+    ];
+    assert_eq!(profiles.detect(&outputs).unwrap().as_deref(), Some("desk"));
+
+    let moved = profiles.load_for_outputs("desk", &outputs).unwrap();
+    assert_eq!(
+        moved.monitor_by_name("DP-2").unwrap().rect(),
+        &Rect::new(2560, 1440, Point::new(0, 0))
+    );
+}
@@ -0,0 +1,120 @@
+use monitor_utils::{Monitor, MonitorSetup, Point, Rect};
+
+fn rect(width: u32, height: u32, x: u32, y: u32) -> Rect {
+    Rect::new(width, height, Point::new(x, y))
+}
+
+#[test]
+fn empty_rects() {
+    assert!(rect(0, 100, 10, 10).is_empty());
+    assert!(rect(100, 0, 10, 10).is_empty());
+    assert!(!rect(1, 1, 0, 0).is_empty());
+
+    assert_eq!(rect(0, 100, 0, 0).area(), 0);
+    assert_eq!(
+        rect(u32::MAX, u32::MAX, 0, 0).area(),
+        u32::MAX as u64 * u32::MAX as u64
+    );
+
+    // an empty Rectangle intersects nothing, even within another Rectangle
+    assert!(rect(0, 10, 5, 5)
+        .intersection(&rect(100, 100, 0, 0))
+        .is_empty());
+}
+
+#[test]
+fn disjoint_and_touching_rects_do_not_intersect() {
+    let base = rect(100, 100, 0, 0);
+
+    for other in [
+        rect(50, 50, 200, 200),
+        rect(50, 50, 0, 300),
+        // sharing the right edge
+        rect(100, 100, 100, 0),
+        // sharing the bottom edge
+        rect(100, 100, 0, 100),
+        // touching at the bottom-right corner
+        rect(100, 100, 100, 100),
+    ] {
+        assert!(base.intersection(&other).is_empty(), "{:?}", other);
+        assert!(other.intersection(&base).is_empty(), "{:?}", other);
+        assert_eq!(base.intersection(&other).area(), 0);
+    }
+}
+
+#[test]
+fn contained_rects_intersect_in_themselves() {
+    let outer = rect(1920, 1080, 0, 0);
+    let inner = rect(300, 200, 100, 50);
+
+    assert_eq!(outer.intersection(&inner), inner);
+    assert_eq!(inner.intersection(&outer), inner);
+    assert_eq!(outer.intersection(&outer), outer);
+}
+
+#[test]
+fn partially_overlapping_rects() {
+    let a = rect(100, 100, 0, 0);
+    let b = rect(100, 100, 60, 30);
+
+    assert_eq!(a.intersection(&b), rect(40, 70, 60, 30));
+    assert_eq!(b.intersection(&a), rect(40, 70, 60, 30));
+    assert_eq!(a.intersection(&b).area(), 2800);
+}
+
+#[test]
+fn rects_reaching_past_the_coordinate_space_do_not_overflow() {
+    let huge = rect(u32::MAX, u32::MAX, u32::MAX - 10, 5);
+    let monitor = rect(1920, 1080, 0, 0);
+
+    assert!(monitor.intersection(&huge).is_empty());
+    assert_eq!(
+        huge.intersection(&rect(100, 100, u32::MAX - 50, 0)),
+        rect(10, 95, u32::MAX - 10, 5)
+    );
+
+    let setup = MonitorSetup::new(vec![Monitor::new("DP-1".to_owned(), 0, monitor)]);
+    assert!(setup.monitor_for_rect(&huge).is_err());
+}
+
+/// Two monitors of the same size side by side.
+fn dual_setup() -> MonitorSetup {
+    MonitorSetup::new(vec![
+        Monitor::new("DP-2".to_owned(), 1, rect(1920, 1080, 1920, 0)),
+        Monitor::new("DP-1".to_owned(), 0, rect(1920, 1080, 0, 0)),
+    ])
+}
+
+#[test]
+fn monitor_for_rect_picks_the_largest_overlap() {
+    let setup = dual_setup();
+
+    let (monitor, overlaps) = setup.monitor_for_rect(&rect(800, 600, 1700, 100)).unwrap();
+    assert_eq!(monitor.name(), "DP-2");
+
+    assert_eq!(overlaps.len(), 2);
+    let areas = overlaps
+        .iter()
+        .map(|overlap| (overlap.monitor().name(), overlap.area()))
+        .collect::<Vec<_>>();
+    assert_eq!(areas, [("DP-1", 220 * 600), ("DP-2", 580 * 600)]);
+    assert!((overlaps[1].fraction() - 580.0 / 800.0).abs() < 1e-9);
+}
+
+#[test]
+fn monitor_for_rect_breaks_ties_in_clockwise_order() {
+    let setup = dual_setup();
+
+    // exactly half on each monitor; DP-1 lies at the origin, so it comes first clockwise
+    let (monitor, overlaps) = setup.monitor_for_rect(&rect(800, 600, 1520, 100)).unwrap();
+    assert_eq!(overlaps[0].area(), overlaps[1].area());
+    assert_eq!(monitor.name(), "DP-1");
+}
+
+#[test]
+fn monitor_for_rect_outside_every_monitor_is_an_error() {
+    let setup = dual_setup();
+
+    assert!(setup.monitor_for_rect(&rect(100, 100, 0, 2000)).is_err());
+    assert!(setup.monitor_for_rect(&rect(0, 0, 10, 10)).is_err());
+}
@@ -0,0 +1,175 @@
+use monitor_utils::{CoordinateSpace, Monitor, MonitorSetup, Point, Rect, Scale};
+
+fn monitor(name: &str, rect: Rect, scale: u32) -> Monitor {
+    Monitor::new(name.to_owned(), 0, rect).with_scale(Scale::from_120ths(scale).unwrap())
+}
+
+/// A 4K laptop panel at scale 2 next to a 1440p monitor at scale 1.25.
+fn hidpi_setup() -> MonitorSetup {
+    MonitorSetup::new(vec![
+        monitor("eDP-1", Rect::new(1920, 1080, Point::new(0, 0)), 240),
+        monitor("DP-1", Rect::new(2048, 1152, Point::new(1920, 0)), 150),
+    ])
+}
+
+#[test]
+fn scale_is_exact_for_common_factors() {
+    for (factor, numerator, formatted) in [
+        (1.0, 120, "1"),
+        (1.25, 150, "1.25"),
+        (1.5, 180, "1.5"),
+        (1.75, 210, "1.75"),
+        (2.0, 240, "2"),
+    ] {
+        let scale = Scale::from_f64(factor).unwrap();
+        assert_eq!(scale.as_120ths(), numerator);
+        assert_eq!(scale.to_f64(), factor);
+        assert_eq!(scale.to_string(), formatted);
+    }
+
+    assert_eq!(Scale::default(), Scale::ONE);
+    assert_eq!(Scale::from_f64(0.0), None);
+    assert_eq!(Scale::from_f64(-1.0), None);
+    assert_eq!(Scale::from_120ths(0), None);
+}
+
+#[test]
+fn physical_rects_do_not_overlap() {
+    let setup = hidpi_setup();
+    let panel = setup.monitor_by_name("eDP-1").unwrap();
+    let external = setup.monitor_by_name("DP-1").unwrap();
+
+    assert_eq!(
+        setup.physical_rect(panel),
+        Rect::new(3840, 2160, Point::new(0, 0))
+    );
+    assert_eq!(
+        setup.rect_in(external, CoordinateSpace::Physical),
+        Rect::new(2560, 1440, Point::new(3840, 0))
+    );
+    assert_eq!(
+        setup.rect_in(panel, CoordinateSpace::Logical),
+        *panel.rect()
+    );
+}
+
+#[test]
+fn unscaled_setup_has_identical_spaces() {
+    let setup = MonitorSetup::new(vec![
+        Monitor::new(
+            "DP-1".to_owned(),
+            0,
+            Rect::new(1920, 1080, Point::new(0, 0)),
+        ),
+        Monitor::new(
+            "DP-2".to_owned(),
+            1,
+            Rect::new(1920, 1080, Point::new(1920, 0)),
+        ),
+    ]);
+
+    for monitor in setup.monitors() {
+        assert_eq!(setup.physical_rect(monitor), *monitor.rect());
+    }
+    let point = Point::new(2500, 700);
+    assert_eq!(setup.to_physical(&point).unwrap(), point);
+    assert_eq!(setup.to_logical(&point).unwrap(), point);
+}
+
+#[test]
+fn points_round_trip_through_physical_space() {
+    let setup = hidpi_setup();
+
+    for x in (0..3968).step_by(7) {
+        let logical = Point::new(x, x % 1080);
+        let physical = setup.to_physical(&logical).unwrap();
+        assert_eq!(setup.to_logical(&physical).unwrap(), logical);
+    }
+}
+
+#[test]
+fn monitor_containing_point_in_either_space() {
+    let setup = hidpi_setup();
+    let point = Point::new(3000, 100);
+
+    let logical = setup
+        .monitor_containing_point_in(&point, CoordinateSpace::Logical)
+        .unwrap();
+    assert_eq!(logical.name(), "DP-1");
+    assert_eq!(setup.monitor_containing_point(&point).unwrap(), logical);
+
+    let physical = setup
+        .monitor_containing_point_in(&point, CoordinateSpace::Physical)
+        .unwrap();
+    assert_eq!(physical.name(), "eDP-1");
+
+    let beyond_logical = Point::new(5000, 0);
+    assert!(setup.monitor_containing_point(&beyond_logical).is_err());
+    assert_eq!(
+        setup
+            .monitor_containing_point_in(&beyond_logical, CoordinateSpace::Physical)
+            .unwrap()
+            .name(),
+        "DP-1"
+    );
+
+    // below the external monitor, which is shorter in physical pixels than the panel
+    assert!(setup.to_logical(&Point::new(4000, 1500)).is_err());
+}
+
+#[test]
+fn center_and_map_point_in_physical_space() {
+    let setup = hidpi_setup();
+    let panel = setup.monitor_by_name("eDP-1").unwrap();
+    let external = setup.monitor_by_name("DP-1").unwrap();
+
+    let center = panel.rect().center();
+    assert_eq!(center, Point::new(960, 540));
+    assert_eq!(
+        setup.to_physical(&center).unwrap(),
+        setup.rect_in(panel, CoordinateSpace::Physical).center()
+    );
+
+    let mapped = setup.map_point_in(
+        &Point::new(1920, 1080),
+        panel,
+        external,
+        CoordinateSpace::Physical,
+    );
+    assert_eq!(mapped, setup.physical_rect(external).center());
+    assert_eq!(
+        setup.map_point(&center, panel, external),
+        external.rect().center()
+    );
+}
+
+#[test]
+fn downscaled_points_stay_on_their_monitor() {
+    // `xrandr --scale 2x2` renders 3840x2160 logical pixels on a 1920x1080 panel
+    let setup = MonitorSetup::new(vec![monitor(
+        "DP-1",
+        Rect::new(3840, 2160, Point::new(0, 0)),
+        60,
+    )]);
+    let physical_rect = setup.physical_rect(&setup.monitors()[0]);
+    assert_eq!(physical_rect, Rect::new(1920, 1080, Point::new(0, 0)));
+
+    for point in [
+        Point::new(0, 0),
+        Point::new(3839, 2159),
+        Point::new(3838, 1),
+    ] {
+        let physical = setup.to_physical(&point).unwrap();
+        assert!(
+            setup
+                .monitor_containing_point_in(&physical, CoordinateSpace::Physical)
+                .is_ok(),
+            "{:?}",
+            physical
+        );
+    }
+    assert_eq!(
+        setup.to_physical(&Point::new(3839, 2159)).unwrap(),
+        Point::new(1919, 1079)
+    );
+}
@@ -0,0 +1,117 @@
+use monitor_utils::{Monitor, MonitorChange, MonitorIdentity, MonitorSetup, Point, Rect, Rotation};
+
+/// Decodes the identity of a Dell display with the given serial number from a minimal EDID.
+fn identity(serial_number: u32) -> MonitorIdentity {
+    let mut edid = [0u8; 128];
+    edid[..8].copy_from_slice(&[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+    // "DEL", as three 5-bit letters
+    edid[8..10].copy_from_slice(&[0x10, 0xac]);
+    edid[10..12].copy_from_slice(&0xa0c1u16.to_le_bytes());
+    edid[12..16].copy_from_slice(&serial_number.to_le_bytes());
+    edid[127] = 0u8.wrapping_sub(edid.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)));
+
+    MonitorIdentity::from_edid(&edid).unwrap()
+}
+
+fn monitor(name: &str, rect: Rect) -> Monitor {
+    Monitor::new(name.to_owned(), 0, rect)
+}
+
+/// A laptop panel with an external monitor to its right.
+fn desk() -> MonitorSetup {
+    MonitorSetup::new(vec![
+        monitor("eDP-1", Rect::new(1920, 1080, Point::new(0, 0))).with_primary(true),
+        monitor("DP-1", Rect::new(2560, 1440, Point::new(1920, 0))).with_identity(identity(1)),
+    ])
+}
+
+fn names<'a>(monitors: impl IntoIterator<Item = &'a &'a Monitor>) -> Vec<&'a str> {
+    monitors.into_iter().map(|monitor| monitor.name()).collect()
+}
+
+fn changed_names<'a>(changes: impl Iterator<Item = &'a MonitorChange<'a>>) -> Vec<&'a str> {
+    changes.map(|change| change.after().name()).collect()
+}
+
+#[test]
+fn identical_setups_have_no_differences() {
+    let (before, after) = (desk(), desk());
+    let diff = before.diff(&after);
+
+    assert!(diff.is_empty());
+    assert!(diff.added().is_empty() && diff.removed().is_empty() && diff.changed().is_empty());
+}
+
+#[test]
+fn hotplugged_monitors_are_added_and_removed() {
+    let before = desk();
+    let after = MonitorSetup::new(vec![
+        monitor("eDP-1", Rect::new(1920, 1080, Point::new(0, 0))).with_primary(true),
+        monitor("HDMI-1", Rect::new(1920, 1080, Point::new(1920, 0))),
+    ]);
+    let diff = before.diff(&after);
+
+    assert_eq!(names(diff.added()), ["HDMI-1"]);
+    assert_eq!(names(diff.removed()), ["DP-1"]);
+    assert!(diff.changed().is_empty());
+
+    let reverse = after.diff(&before);
+    assert_eq!(names(reverse.added()), ["DP-1"]);
+    assert_eq!(names(reverse.removed()), ["HDMI-1"]);
+}
+
+#[test]
+fn changes_are_categorized() {
+    let before = desk();
+    let after = MonitorSetup::new(vec![
+        monitor("eDP-1", Rect::new(1920, 1080, Point::new(1440, 0))),
+        monitor("DP-1", Rect::new(1440, 2560, Point::new(0, 0)))
+            .with_rotation(Rotation::Left)
+            .with_identity(identity(1))
+            .with_primary(true),
+    ]);
+    let diff = before.diff(&after);
+
+    assert!(diff.added().is_empty() && diff.removed().is_empty());
+    assert_eq!(diff.changed().len(), 2);
+
+    assert_eq!(changed_names(diff.moved()), ["DP-1", "eDP-1"]);
+    assert_eq!(changed_names(diff.resized()), ["DP-1"]);
+    assert_eq!(changed_names(diff.rotated()), ["DP-1"]);
+    assert_eq!(changed_names(diff.primary_changed()), ["DP-1", "eDP-1"]);
+
+    let external = diff
+        .changed()
+        .iter()
+        .find(|change| change.after().name() == "DP-1")
+        .unwrap();
+    assert_eq!(external.before().rect().offset(), Point::new(1920, 0));
+    assert!(!external.before().is_primary() && external.after().is_primary());
+}
+
+#[test]
+fn displays_are_followed_across_ports() {
+    let before = desk();
+    let after = MonitorSetup::new(vec![
+        monitor("eDP-1", Rect::new(1920, 1080, Point::new(0, 0))).with_primary(true),
+        monitor("HDMI-1", Rect::new(2560, 1440, Point::new(1920, 0))).with_identity(identity(1)),
+    ]);
+
+    // the display moved from DP-1 to HDMI-1, keeping its place in the layout
+    assert!(before.diff(&after).is_empty());
+}
+
+#[test]
+fn another_display_on_the_same_port_is_a_different_monitor() {
+    let before = desk();
+    let after = MonitorSetup::new(vec![
+        monitor("eDP-1", Rect::new(1920, 1080, Point::new(0, 0))).with_primary(true),
+        monitor("DP-1", Rect::new(2560, 1440, Point::new(1920, 0))).with_identity(identity(2)),
+    ]);
+    let diff = before.diff(&after);
+
+    assert_eq!(diff.added().len(), 1);
+    assert_eq!(diff.added()[0].identity(), Some(&identity(2)));
+    assert_eq!(diff.removed().len(), 1);
+    assert_eq!(diff.removed()[0].identity(), Some(&identity(1)));
+}
@@ -0,0 +1,158 @@
+use monitor_utils::{Monitor, MonitorSetup, Point, Rect, TraversalOrder};
+
+fn monitor(name: &str, width: u32, height: u32, x: u32, y: u32, xinerama: u32) -> Monitor {
+    Monitor::new(
+        name.to_owned(),
+        0,
+        Rect::new(width, height, Point::new(x, y)),
+    )
+    .with_xinerama_index(xinerama)
+}
+
+/// An asymmetric layout: a monitor hanging lower on the left, a large monitor in the middle with
+/// a small one below it, and a portrait monitor on the right.
+///
+/// ```text
+///         +--------------+   +----+
+/// +-------+    eDP-1     |   |    |
+/// | DP-3  |              |   |DP-1|
+/// |       +----+---------+   |    |
+/// +-------+    | HDMI-1  |   |    |
+///              +---------+   +----+
+/// ```
+fn setup_in(order: &TraversalOrder) -> MonitorSetup {
+    let mut setup = MonitorSetup::new(vec![
+        monitor("HDMI-1", 1280, 1024, 2560, 1440, 3),
+        monitor("DP-3", 1920, 1080, 0, 360, 2),
+        monitor("DP-1", 1080, 1920, 4480, 0, 0),
+        monitor("eDP-1", 2560, 1440, 1920, 0, 1),
+    ]);
+    setup.set_traversal_order(order);
+
+    setup
+}
+
+/// Yields the names of the monitors, following `next_monitor_clockwise` from the first monitor.
+fn traversal(order: TraversalOrder) -> Vec<String> {
+    let setup = setup_in(&order);
+    let first = &setup.monitors()[0];
+    let mut names = vec![first.name().to_owned()];
+    let mut monitor = setup.next_monitor_clockwise(first).unwrap();
+
+    while monitor != first {
+        names.push(monitor.name().to_owned());
+        monitor = setup.next_monitor_clockwise(monitor).unwrap();
+    }
+
+    // the stored order and the traversal agree
+    let stored = setup
+        .monitors()
+        .iter()
+        .map(|monitor| monitor.name().to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(names, stored);
+
+    names
+}
+
+#[test]
+fn clockwise_from_origin() {
+    assert_eq!(
+        traversal(TraversalOrder::ClockwiseFromOrigin),
+        ["eDP-1", "DP-1", "HDMI-1", "DP-3"]
+    );
+    assert_eq!(
+        traversal(TraversalOrder::default()),
+        traversal(TraversalOrder::ClockwiseFromOrigin)
+    );
+}
+
+#[test]
+fn clockwise_around_centroid() {
+    assert_eq!(
+        traversal(TraversalOrder::ClockwiseAroundCentroid),
+        ["eDP-1", "DP-1", "HDMI-1", "DP-3"]
+    );
+}
+
+#[test]
+fn row_major() {
+    // DP-3, eDP-1 and DP-1 share a row, as their centers lie within eDP-1
+    assert_eq!(
+        traversal(TraversalOrder::RowMajor),
+        ["DP-3", "eDP-1", "DP-1", "HDMI-1"]
+    );
+}
+
+#[test]
+fn column_major() {
+    assert_eq!(
+        traversal(TraversalOrder::ColumnMajor),
+        ["DP-3", "eDP-1", "HDMI-1", "DP-1"]
+    );
+}
+
+#[test]
+fn by_name() {
+    assert_eq!(
+        traversal(TraversalOrder::ByName),
+        ["DP-1", "DP-3", "HDMI-1", "eDP-1"]
+    );
+}
+
+#[test]
+fn by_xinerama_index() {
+    assert_eq!(
+        traversal(TraversalOrder::ByXineramaIndex),
+        ["DP-1", "eDP-1", "DP-3", "HDMI-1"]
+    );
+}
+
+#[test]
+fn explicit_order_lists_the_named_monitors_first() {
+    let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+
+    assert_eq!(
+        traversal(TraversalOrder::Explicit(names(&["HDMI-1", "DP-3"]))),
+        ["HDMI-1", "DP-3", "eDP-1", "DP-1"]
+    );
+
+    // unknown names are skipped, and the unlisted monitors follow in clockwise order
+    assert_eq!(
+        traversal(TraversalOrder::Explicit(names(&["VGA-1", "DP-1"]))),
+        ["DP-1", "eDP-1", "HDMI-1", "DP-3"]
+    );
+    assert_eq!(
+        traversal(TraversalOrder::Explicit(vec![])),
+        traversal(TraversalOrder::ClockwiseFromOrigin)
+    );
+}
+
+#[test]
+fn counterclockwise_reverses_the_order() {
+    let setup = setup_in(&TraversalOrder::ByName);
+    let first = setup.monitor_by_name("DP-1").unwrap();
+
+    assert_eq!(
+        setup.next_monitor_counterclockwise(first).unwrap().name(),
+        "eDP-1"
+    );
+    assert_eq!(setup.next_monitor_clockwise(first).unwrap().name(), "DP-3");
+}
+
+#[test]
+fn monitors_reaching_past_the_end_of_the_coordinate_space_share_a_column() {
+    let mut setup = MonitorSetup::new(vec![
+        monitor("DP-2", 10, 10, u32::MAX - 5, 10, 2),
+        monitor("DP-1", 10, 10, u32::MAX - 5, 0, 1),
+        monitor("eDP-1", 10, 10, 0, 0, 0),
+    ]);
+    setup.set_traversal_order(&TraversalOrder::ColumnMajor);
+
+    let names = setup
+        .monitors()
+        .iter()
+        .map(|monitor| monitor.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["eDP-1", "DP-1", "DP-2"]);
+}
@@ -0,0 +1,202 @@
+use monitor_utils::{LayoutIssue, Mode, Monitor, MonitorSetup, Point, Rect};
+
+fn monitor(name: &str, width: u32, height: u32, x: u32, y: u32) -> Monitor {
+    Monitor::new(
+        name.to_owned(),
+        0,
+        Rect::new(width, height, Point::new(x, y)),
+    )
+}
+
+#[test]
+fn clean_layout_has_no_issues() {
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-1", 1920, 1080, 0, 0),
+        monitor("DP-2", 2560, 1440, 1920, 0),
+    ]);
+    let report = setup.validate();
+
+    assert!(report.issues().is_empty());
+    assert!(!report.has_errors());
+}
+
+#[test]
+fn zero_size_is_an_error() {
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-1", 1920, 1080, 0, 0),
+        monitor("DP-2", 0, 1080, 1920, 0),
+    ]);
+    let report = setup.validate();
+
+    assert_eq!(
+        report.issues(),
+        [LayoutIssue::ZeroSize {
+            monitor: "DP-2".to_owned()
+        }]
+    );
+    assert!(report.has_errors());
+    assert_eq!(report.issues()[0].to_string(), "DP-2 has zero size");
+}
+
+#[test]
+fn overlap_is_an_error() {
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-1", 1920, 1080, 0, 0),
+        monitor("DP-2", 1920, 1080, 1820, 0),
+    ]);
+    let report = setup.validate();
+
+    assert_eq!(
+        report.issues(),
+        [LayoutIssue::Overlap {
+            first: "DP-1".to_owned(),
+            second: "DP-2".to_owned(),
+            area: 100 * 1080,
+        }]
+    );
+    assert!(report.has_errors());
+    assert_eq!(
+        report.issues()[0].to_string(),
+        "DP-1 overlaps DP-2 by 108000 pixels"
+    );
+}
+
+#[test]
+fn mirrors_are_a_warning() {
+    let setup = MonitorSetup::new(vec![
+        monitor("eDP-1", 1920, 1080, 0, 0),
+        monitor("HDMI-1", 1920, 1080, 0, 0),
+    ]);
+    let report = setup.validate();
+
+    assert_eq!(
+        report.issues(),
+        [LayoutIssue::Mirrored {
+            first: "eDP-1".to_owned(),
+            second: "HDMI-1".to_owned(),
+        }]
+    );
+    assert!(!report.issues()[0].is_error());
+    assert!(!report.has_errors());
+}
+
+#[test]
+fn below_native_resolution_is_a_warning() {
+    let setup = MonitorSetup::new(vec![monitor("HDMI-1", 1280, 1024, 0, 0).with_modes(vec![
+        Mode::new(1920, 1080, 60000).with_preferred(true),
+        Mode::new(1280, 1024, 60020).with_current(true),
+    ])]);
+    let report = setup.validate();
+
+    assert_eq!(report.issues().len(), 1);
+    assert!(matches!(
+        &report.issues()[0],
+        LayoutIssue::BelowNativeResolution { monitor, current, native }
+            if monitor == "HDMI-1" && current.width() == 1280 && native.width() == 1920
+    ));
+    assert!(!report.has_errors());
+    assert_eq!(
+        report.issues()[0].to_string(),
+        "HDMI-1 runs at 1280x1024, below its native resolution of 1920x1080"
+    );
+}
+
+#[test]
+fn gap_reports_the_distance_between_the_closest_monitors() {
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-1", 1920, 1080, 0, 0),
+        monitor("DP-2", 1920, 1080, 2000, 1200),
+    ]);
+    let report = setup.validate();
+
+    assert_eq!(
+        report.issues()[0],
+        LayoutIssue::Gap {
+            first: "DP-1".to_owned(),
+            second: "DP-2".to_owned(),
+            horizontal: 80,
+            vertical: 120,
+        }
+    );
+    assert_eq!(
+        report.issues()[0].to_string(),
+        "gap of 80x120 pixels between DP-1 and DP-2"
+    );
+    assert!(report.has_errors());
+}
+
+#[test]
+fn islands_are_listed_in_clockwise_order() {
+    // two touching monitors, and a third one far off to their right
+    let setup = MonitorSetup::new(vec![
+        monitor("HDMI-1", 1920, 1080, 5000, 0),
+        monitor("DP-2", 1920, 1080, 1920, 0),
+        monitor("DP-1", 1920, 1080, 0, 0),
+    ]);
+    let report = setup.validate();
+
+    assert_eq!(
+        report.issues(),
+        [
+            LayoutIssue::Gap {
+                first: "DP-2".to_owned(),
+                second: "HDMI-1".to_owned(),
+                horizontal: 5000 - 3840,
+                vertical: 0,
+            },
+            LayoutIssue::DisconnectedIslands {
+                islands: vec![
+                    vec!["DP-1".to_owned(), "DP-2".to_owned()],
+                    vec!["HDMI-1".to_owned()],
+                ],
+            },
+        ]
+    );
+    assert!(report.issues().iter().all(LayoutIssue::is_error));
+    assert_eq!(
+        report.issues()[1].to_string(),
+        "monitors form disconnected islands: [DP-1, DP-2] [HDMI-1]"
+    );
+}
+
+#[test]
+fn monitors_touching_at_a_corner_are_islands() {
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-1", 1920, 1080, 0, 0),
+        monitor("DP-2", 1920, 1080, 1920, 1080),
+    ]);
+    let report = setup.validate();
+
+    assert!(report.has_errors());
+    assert!(matches!(
+        report.issues()[0],
+        LayoutIssue::Gap {
+            horizontal: 0,
+            vertical: 0,
+            ..
+        }
+    ));
+    assert!(matches!(
+        &report.issues()[1],
+        LayoutIssue::DisconnectedIslands { islands } if islands.len() == 2
+    ));
+}
+
+#[test]
+fn monitors_reaching_past_the_end_of_the_coordinate_space_are_clipped() {
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-1", 10, 10, u32::MAX - 5, 0),
+        monitor("DP-2", 10, 10, 0, 0),
+    ]);
+    let report = setup.validate();
+
+    assert_eq!(
+        report.issues()[0],
+        LayoutIssue::Gap {
+            first: "DP-2".to_owned(),
+            second: "DP-1".to_owned(),
+            horizontal: u32::MAX - 15,
+            vertical: 0,
+        }
+    );
+}
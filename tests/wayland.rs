@@ -0,0 +1,304 @@
+//! These tests run the sway and Hyprland loaders against the outputs in `tests/fixtures/wayland`,
+//! which follow the formats documented in sway-ipc(7) and by the Hyprland wiki, and against fake
+//! IPC sockets speaking the sway protocol and the Hyprland event protocol.
+#![cfg(feature = "wayland")]
+
+use monitor_utils::{
+    wayland::{
+        parse_hyprland_monitors, parse_sway_outputs, HyprlandEvents, HyprlandMonitorLoader,
+        SwayEvents, SwayMonitorLoader,
+    },
+    LoadMonitors, Mode, Point, Rect, Reflection, Rotation, Scale,
+};
+
+use std::io::{ErrorKind, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::thread::JoinHandle;
+
+const SWAY_OUTPUTS: &str = include_str!("fixtures/wayland/sway-outputs.json");
+const HYPRLAND_MONITORS: &str = include_str!("fixtures/wayland/hyprland-monitors.json");
+
+const IPC_SUBSCRIBE: u32 = 2;
+const IPC_GET_OUTPUTS: u32 = 3;
+const IPC_WORKSPACE_EVENT: u32 = 0x8000_0000;
+const IPC_OUTPUT_EVENT: u32 = 0x8000_0001;
+
+/// Yields the path of a socket in a fresh directory, unique to the test.
+fn socket_path(test: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "monitor-utils-wayland-{}-{}",
+        std::process::id(),
+        test
+    ));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+
+    directory.join("socket")
+}
+
+/// Serves a single connection on a fresh socket with the given function, on another thread.
+fn serve(
+    test: &str,
+    server: impl FnOnce(UnixStream) + Send + 'static,
+) -> (PathBuf, JoinHandle<()>) {
+    let path = socket_path(test);
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        server(stream);
+    });
+
+    (path, handle)
+}
+
+fn write_sway_message(stream: &mut UnixStream, message_type: u32, payload: &str) {
+    let mut message = b"i3-ipc".to_vec();
+    message.extend((payload.len() as u32).to_ne_bytes());
+    message.extend(message_type.to_ne_bytes());
+    message.extend(payload.as_bytes());
+
+    stream.write_all(&message).unwrap();
+}
+
+fn read_sway_message(stream: &mut UnixStream) -> (u32, String) {
+    let mut header = [0; 14];
+    stream.read_exact(&mut header).unwrap();
+    assert_eq!(&header[..6], b"i3-ipc");
+
+    let length = u32::from_ne_bytes(header[6..10].try_into().unwrap());
+    let message_type = u32::from_ne_bytes(header[10..14].try_into().unwrap());
+    let mut payload = vec![0; length as usize];
+    stream.read_exact(&mut payload).unwrap();
+
+    (message_type, String::from_utf8(payload).unwrap())
+}
+
+#[test]
+fn sway_outputs_are_laid_out_from_the_origin() {
+    let monitors = parse_sway_outputs(SWAY_OUTPUTS).unwrap();
+
+    let names = monitors.iter().map(|m| m.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["eDP-1", "DP-1", "HDMI-A-1"]);
+
+    // HDMI-A-1 is at y = -300, above the other outputs
+    assert_eq!(
+        monitors[0].rect(),
+        &Rect::new(1440, 900, Point::new(0, 840))
+    );
+    assert_eq!(
+        monitors[1].rect(),
+        &Rect::new(2560, 1440, Point::new(1440, 300))
+    );
+    assert_eq!(
+        monitors[2].rect(),
+        &Rect::new(1080, 1920, Point::new(4000, 0))
+    );
+
+    let crtcs = monitors.iter().map(|m| m.crtc()).collect::<Vec<_>>();
+    assert_eq!(crtcs, [0, 1, 2]);
+}
+
+#[test]
+fn sway_outputs_keep_their_scale_transform_and_modes() {
+    let monitors = parse_sway_outputs(SWAY_OUTPUTS).unwrap();
+
+    assert_eq!(monitors[0].scale(), Scale::from_120ths(240).unwrap());
+    assert_eq!(monitors[1].scale(), Scale::ONE);
+
+    assert_eq!(monitors[1].rotation(), Rotation::Normal);
+    assert_eq!(monitors[2].rotation(), Rotation::Right);
+    assert_eq!(monitors[2].reflection(), Reflection::Normal);
+
+    assert_eq!(
+        monitors[2].modes(),
+        [
+            Mode::new(1920, 1200, 59950),
+            Mode::new(1920, 1080, 60000).with_current(true),
+        ]
+    );
+    assert_eq!(
+        monitors[0].current_mode(),
+        Some(&Mode::new(2880, 1800, 60001).with_current(true))
+    );
+}
+
+#[test]
+fn sway_flipped_transforms_are_reflected() {
+    let json = r#"[{"name": "DP-1", "active": true, "transform": "flipped-270",
+        "rect": {"x": 0, "y": 0, "width": 1080, "height": 1920}}]"#;
+    let monitors = parse_sway_outputs(json).unwrap();
+
+    assert_eq!(monitors[0].rotation(), Rotation::Left);
+    assert_eq!(monitors[0].reflection(), Reflection::X);
+}
+
+#[test]
+fn sway_invalid_transform_is_an_error() {
+    let json = r#"[{"name": "DP-1", "active": true, "transform": "45",
+        "rect": {"x": 0, "y": 0, "width": 1920, "height": 1080}}]"#;
+    let error = parse_sway_outputs(json).unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn hyprland_monitors_are_laid_out_in_logical_pixels() {
+    let monitors = parse_hyprland_monitors(HYPRLAND_MONITORS).unwrap();
+
+    let names = monitors.iter().map(|m| m.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["eDP-1", "DP-1", "HDMI-A-1"]);
+
+    // eDP-1 is scaled by 1.5, and HDMI-A-1 is rotated and at y = -120
+    assert_eq!(
+        monitors[0].rect(),
+        &Rect::new(1920, 1200, Point::new(0, 360))
+    );
+    assert_eq!(
+        monitors[1].rect(),
+        &Rect::new(2560, 1440, Point::new(1920, 120))
+    );
+    assert_eq!(
+        monitors[2].rect(),
+        &Rect::new(1200, 1920, Point::new(4480, 0))
+    );
+
+    assert_eq!(monitors[0].scale(), Scale::from_120ths(180).unwrap());
+    assert_eq!(monitors[2].rotation(), Rotation::Left);
+    assert_eq!(monitors[2].reflection(), Reflection::Normal);
+}
+
+#[test]
+fn hyprland_current_modes_match_the_refresh_rate() {
+    let monitors = parse_hyprland_monitors(HYPRLAND_MONITORS).unwrap();
+
+    // the refresh rate is reported more precisely than the modes
+    assert_eq!(
+        monitors[0].modes(),
+        [
+            Mode::new(2880, 1800, 120000).with_current(true),
+            Mode::new(2880, 1800, 60000),
+        ]
+    );
+    assert_eq!(
+        monitors[1].current_mode(),
+        Some(&Mode::new(2560, 1440, 59950).with_current(true))
+    );
+}
+
+#[test]
+fn hyprland_flipped_transforms_are_reflected() {
+    let json = r#"[{"name": "DP-1", "width": 1920, "height": 1080, "refreshRate": 60.0,
+        "x": 0, "y": 0, "scale": 1.0, "transform": 5}]"#;
+    let monitors = parse_hyprland_monitors(json).unwrap();
+
+    assert_eq!(monitors[0].rotation(), Rotation::Right);
+    assert_eq!(monitors[0].reflection(), Reflection::X);
+    assert_eq!(monitors[0].rect(), &Rect::new(1080, 1920, Point::new(0, 0)));
+}
+
+#[test]
+fn hyprland_invalid_transform_is_an_error() {
+    let json = r#"[{"name": "DP-1", "width": 1920, "height": 1080, "refreshRate": 60.0,
+        "x": 0, "y": 0, "scale": 1.0, "transform": 8}]"#;
+    let error = parse_hyprland_monitors(json).unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn sway_loader_queries_the_outputs() {
+    let (path, server) = serve("sway-loader", |mut stream| {
+        assert_eq!(
+            read_sway_message(&mut stream),
+            (IPC_GET_OUTPUTS, String::new())
+        );
+        write_sway_message(&mut stream, IPC_GET_OUTPUTS, SWAY_OUTPUTS);
+    });
+
+    let monitors = SwayMonitorLoader::with_socket(&path)
+        .load_monitors()
+        .unwrap();
+    server.join().unwrap();
+
+    assert_eq!(monitors, parse_sway_outputs(SWAY_OUTPUTS).unwrap());
+}
+
+#[test]
+fn sway_output_events_are_changes() {
+    let (path, server) = serve("sway-events", |mut stream| {
+        assert_eq!(
+            read_sway_message(&mut stream),
+            (IPC_SUBSCRIBE, r#"["output"]"#.to_owned())
+        );
+        write_sway_message(&mut stream, IPC_SUBSCRIBE, r#"{"success": true}"#);
+
+        write_sway_message(&mut stream, IPC_WORKSPACE_EVENT, r#"{"change": "focus"}"#);
+        write_sway_message(
+            &mut stream,
+            IPC_OUTPUT_EVENT,
+            r#"{"change": "unspecified"}"#,
+        );
+    });
+
+    let mut events = SwayEvents::with_socket(&path).unwrap();
+    events.wait_for_change().unwrap();
+    server.join().unwrap();
+
+    // sway closed the socket
+    assert!(events.wait_for_change().is_err());
+}
+
+#[test]
+fn sway_rejected_subscription_is_an_error() {
+    let (path, server) = serve("sway-rejected", |mut stream| {
+        read_sway_message(&mut stream);
+        write_sway_message(&mut stream, IPC_SUBSCRIBE, r#"{"success": false}"#);
+    });
+
+    assert!(SwayEvents::with_socket(&path).is_err());
+    server.join().unwrap();
+}
+
+#[test]
+fn hyprland_loader_queries_the_monitors() {
+    let (path, server) = serve("hyprland-loader", |mut stream| {
+        let mut request = String::new();
+        stream.read_to_string(&mut request).unwrap();
+        assert_eq!(request, "j/monitors");
+
+        stream.write_all(HYPRLAND_MONITORS.as_bytes()).unwrap();
+    });
+
+    let monitors = HyprlandMonitorLoader::with_socket(&path)
+        .load_monitors()
+        .unwrap();
+    server.join().unwrap();
+
+    assert_eq!(
+        monitors,
+        parse_hyprland_monitors(HYPRLAND_MONITORS).unwrap()
+    );
+}
+
+#[test]
+fn hyprland_monitor_events_are_changes() {
+    let (path, server) = serve("hyprland-events", |mut stream| {
+        stream
+            .write_all(b"workspace>>2\nactivewindow>>kitty,~\nmonitoraddedv2>>2,HDMI-A-1,EV2456\n")
+            .unwrap();
+        stream
+            .write_all(b"focusedmon>>HDMI-A-1,3\nmonitorremoved>>HDMI-A-1\n")
+            .unwrap();
+    });
+
+    let mut events = HyprlandEvents::with_socket(&path).unwrap();
+    events.wait_for_change().unwrap();
+    events.wait_for_change().unwrap();
+    server.join().unwrap();
+
+    // Hyprland closed the socket
+    let error = events.wait_for_change().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,98 @@
+//! These tests require a running X server without a window manager, and are therefore ignored by
+//! default. Run them under Xvfb with:
+//!
+//! ```sh
+//! xvfb-run cargo test --test x11_window -- --ignored
+//! ```
+#![cfg(feature = "x11")]
+
+use monitor_utils::{x11::X11Windows, Point, Rect};
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt, CreateWindowAux, PropMode, WindowClass};
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+
+/// Creates and maps a window with the given geometry, returning its ID.
+fn create_window(connection: &RustConnection, root: u32, rect: &Rect) -> u32 {
+    let window = connection.generate_id().unwrap();
+    connection
+        .create_window(
+            x11rb::COPY_DEPTH_FROM_PARENT,
+            window,
+            root,
+            rect.offset().x() as i16,
+            rect.offset().y() as i16,
+            rect.width() as u16,
+            rect.height() as u16,
+            0,
+            WindowClass::INPUT_OUTPUT,
+            x11rb::COPY_FROM_PARENT,
+            &CreateWindowAux::new(),
+        )
+        .unwrap();
+    connection.map_window(window).unwrap();
+    connection.sync().unwrap();
+
+    window
+}
+
+#[test]
+#[ignore = "requires an X server"]
+fn frame_rect_of_undecorated_window() {
+    let (connection, screen_num) = x11rb::connect(None).unwrap();
+    let root = connection.setup().roots[screen_num].root;
+
+    let rect = Rect::new(300, 200, Point::new(100, 50));
+    let window = create_window(&connection, root, &rect);
+
+    let windows = X11Windows::new().unwrap();
+    assert_eq!(windows.frame_rect(window).unwrap(), rect);
+}
+
+#[test]
+#[ignore = "requires an X server"]
+fn active_window_is_read_from_root() {
+    let (connection, screen_num) = x11rb::connect(None).unwrap();
+    let root = connection.setup().roots[screen_num].root;
+
+    let window = create_window(&connection, root, &Rect::new(10, 10, Point::new(0, 0)));
+
+    // there is no window manager under Xvfb, so set the hint ourselves
+    let active_window = connection
+        .intern_atom(false, b"_NET_ACTIVE_WINDOW")
+        .unwrap()
+        .reply()
+        .unwrap()
+        .atom;
+    connection
+        .change_property32(
+            PropMode::REPLACE,
+            root,
+            active_window,
+            AtomEnum::WINDOW,
+            &[window],
+        )
+        .unwrap();
+    connection.sync().unwrap();
+
+    let windows = X11Windows::new().unwrap();
+    assert_eq!(windows.active_window().unwrap(), window);
+}
+
+#[test]
+#[ignore = "requires an X server"]
+fn move_resize_without_window_manager() {
+    let (connection, screen_num) = x11rb::connect(None).unwrap();
+    let root = connection.setup().roots[screen_num].root;
+
+    let window = create_window(&connection, root, &Rect::new(10, 10, Point::new(0, 0)));
+
+    // without a window manager, `_NET_MOVERESIZE_WINDOW` is unsupported and the window is
+    // configured directly
+    let target = Rect::new(640, 480, Point::new(200, 100));
+    let windows = X11Windows::new().unwrap();
+    windows.move_resize(window, &target).unwrap();
+
+    assert_eq!(windows.frame_rect(window).unwrap(), target);
+}
@@ -0,0 +1,51 @@
+use monitor_utils::{Monitor, MonitorSetup, Point, Rect};
+
+fn monitor(name: &str, crtc: u32, x: u32) -> Monitor {
+    Monitor::new(
+        name.to_owned(),
+        crtc,
+        Rect::new(1920, 1080, Point::new(x, 0)),
+    )
+}
+
+fn index_of(setup: &MonitorSetup, name: &str) -> Option<u32> {
+    setup.monitor_by_name(name).unwrap().xinerama_index()
+}
+
+#[test]
+fn derived_indices_put_the_primary_first_then_follow_crtcs() {
+    // reported in neither CRTC nor clockwise order
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-2", 2, 0),
+        monitor("eDP-1", 0, 1920),
+        monitor("HDMI-1", 3, 3840).with_primary(true),
+        monitor("DP-1", 1, 5760),
+    ]);
+
+    assert_eq!(index_of(&setup, "HDMI-1"), Some(0));
+    assert_eq!(index_of(&setup, "eDP-1"), Some(1));
+    assert_eq!(index_of(&setup, "DP-1"), Some(2));
+    assert_eq!(index_of(&setup, "DP-2"), Some(3));
+
+    assert_eq!(setup.monitor_by_xinerama_index(2).unwrap().name(), "DP-1");
+    assert!(setup.monitor_by_xinerama_index(4).is_err());
+}
+
+#[test]
+fn derived_indices_without_primary_follow_crtcs() {
+    let setup = MonitorSetup::new(vec![monitor("DP-1", 1, 0), monitor("eDP-1", 0, 1920)]);
+
+    assert_eq!(index_of(&setup, "eDP-1"), Some(0));
+    assert_eq!(index_of(&setup, "DP-1"), Some(1));
+}
+
+#[test]
+fn indices_reported_by_the_loader_are_kept() {
+    let setup = MonitorSetup::new(vec![
+        monitor("eDP-1", 0, 0).with_xinerama_index(1),
+        monitor("DP-1", 1, 1920).with_xinerama_index(0),
+    ]);
+
+    assert_eq!(index_of(&setup, "eDP-1"), Some(1));
+    assert_eq!(index_of(&setup, "DP-1"), Some(0));
+}
@@ -0,0 +1,112 @@
+//! These tests compare the arguments yielded by `MonitorSetup::to_xrandr_args` with the `.golden`
+//! files in `tests/fixtures/xrandr-args`, which list one `--output` per line.
+//! Run with `UPDATE_GOLDEN=1` to rewrite the golden files after an intended change.
+#![cfg(feature = "x11")]
+
+use monitor_utils::x11::parse_xrandr_output;
+use monitor_utils::{Monitor, MonitorSetup, Point, Rect, Reflection, Rotation, Scale};
+
+fn golden_path(name: &str) -> String {
+    format!(
+        "{}/tests/fixtures/xrandr-args/{}.golden",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+fn assert_golden(name: &str, setup: &MonitorSetup) {
+    let actual = setup
+        .to_xrandr_args()
+        .join(" ")
+        .replace(" --output", "\n--output")
+        + "\n";
+    let golden_path = golden_path(name);
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(&golden_path, &actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&golden_path).unwrap();
+    assert_eq!(actual, expected, "{} does not match {}.golden", name, name);
+}
+
+/// Parses one of the xrandr fixtures used by the parser tests.
+fn parsed_fixture(name: &str) -> MonitorSetup {
+    let path = format!(
+        "{}/tests/fixtures/xrandr/{}.txt",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    let monitors = parse_xrandr_output(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+    MonitorSetup::new(monitors)
+}
+
+fn monitor(name: &str, width: u32, height: u32, x: u32, y: u32) -> Monitor {
+    Monitor::new(
+        name.to_owned(),
+        0,
+        Rect::new(width, height, Point::new(x, y)),
+    )
+}
+
+#[test]
+fn side_by_side() {
+    let setup = MonitorSetup::new(vec![
+        monitor("eDP-1", 1920, 1080, 0, 0).with_primary(true),
+        monitor("HDMI-1", 2560, 1440, 1920, 0),
+    ]);
+
+    assert_golden("side-by-side", &setup);
+}
+
+#[test]
+fn sideways_modes_are_swapped() {
+    let setup = MonitorSetup::new(vec![
+        monitor("DP-1", 1080, 1920, 0, 0).with_rotation(Rotation::Left),
+        monitor("DP-2", 1920, 1080, 1080, 420)
+            .with_rotation(Rotation::Inverted)
+            .with_reflection(Reflection::X),
+        monitor("HDMI-1", 1200, 1920, 3000, 0)
+            .with_rotation(Rotation::Right)
+            .with_reflection(Reflection::XY)
+            .with_primary(true),
+    ]);
+
+    assert_golden("sideways", &setup);
+}
+
+#[test]
+fn scaled_modes_use_the_inverse_factor() {
+    let setup = MonitorSetup::new(vec![
+        monitor("eDP-1", 1920, 1080, 0, 0).with_scale(Scale::from_120ths(240).unwrap()),
+        monitor("DP-1", 2048, 1152, 1920, 0).with_scale(Scale::from_120ths(150).unwrap()),
+        // a sideways scaled monitor, whose physical mode is swapped after scaling
+        monitor("DP-2", 864, 1536, 3968, 0)
+            .with_scale(Scale::from_120ths(150).unwrap())
+            .with_rotation(Rotation::Right),
+    ]);
+
+    assert_golden("scaled", &setup);
+}
+
+#[test]
+fn every_mirror_gets_the_full_arguments() {
+    let setup = MonitorSetup::new(vec![
+        monitor("eDP-1", 1920, 1080, 0, 0),
+        monitor("HDMI-1", 1920, 1080, 0, 0).with_primary(true),
+        monitor("DP-1", 1920, 1080, 0, 0),
+        monitor("DP-2", 1920, 1080, 1920, 0),
+    ]);
+
+    // the group is primary, but only its own name is passed `--primary`
+    assert!(setup.monitor_by_name("eDP-1").unwrap().is_primary());
+    assert_golden("mirrored", &setup);
+}
+
+#[test]
+fn parsed_fixtures_round_trip() {
+    assert_golden("rotated-fixture", &parsed_fixture("rotated"));
+    assert_golden("scaled-fixture", &parsed_fixture("scaled"));
+}
@@ -0,0 +1,40 @@
+//! These tests require a running X server supporting RandR, and are therefore ignored by default.
+//! Run them under Xvfb with:
+//!
+//! ```sh
+//! xvfb-run cargo test --test xrandr_events -- --ignored
+//! ```
+#![cfg(feature = "x11")]
+
+use monitor_utils::x11::XRandrEvents;
+
+use x11rb::connection::Connection;
+use x11rb::protocol::randr::ConnectionExt;
+
+#[test]
+#[ignore = "requires an X server"]
+fn resizing_the_screen_is_a_change() {
+    let events = XRandrEvents::new().unwrap();
+
+    let (connection, screen_num) = x11rb::connect(None).unwrap();
+    let screen = &connection.setup().roots[screen_num];
+    let (root, width, height) = (screen.root, screen.width_in_pixels, screen.height_in_pixels);
+    let (mm_width, mm_height) = (
+        screen.width_in_millimeters as u32,
+        screen.height_in_millimeters as u32,
+    );
+
+    connection
+        .randr_set_screen_size(root, width - 16, height - 16, mm_width, mm_height)
+        .unwrap()
+        .check()
+        .unwrap();
+    events.wait_for_change().unwrap();
+
+    connection
+        .randr_set_screen_size(root, width, height, mm_width, mm_height)
+        .unwrap()
+        .check()
+        .unwrap();
+    events.wait_for_change().unwrap();
+}
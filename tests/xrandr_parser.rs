@@ -0,0 +1,384 @@
+//! These tests run the xrandr parser against the outputs of `xrandr --current --verbose` in
+//! `tests/fixtures/xrandr`, which cover the naming schemes of the common drivers, and compare the
+//! result with the `.golden` file next to each fixture.
+//! The driver fixtures are reconstructed from the output of each driver, with complete EDIDs
+//! (including a CTA-861 extension block for HDMI displays) naming displays whose native
+//! resolution and size match the preferred mode and the reported size, and with the standard
+//! DMT, CEA-861 or CVT reduced blanking timings of each mode, rather than captured from hardware;
+//! prefer real captures when adding or replacing fixtures.
+//! Run with `UPDATE_GOLDEN=1` to rewrite the golden files after an intended change.
+#![cfg(feature = "x11")]
+
+use monitor_utils::x11::{
+    parse_xrandr_connected_outputs, parse_xrandr_output, parse_xrandr_outputs, XRandrParseError,
+};
+use monitor_utils::{
+    ConnectionState, LayoutIssue, Mode, MonitorSetup, OutputInventory, Point, Rect, Reflection,
+    Rotation, Scale,
+};
+
+use std::fmt::Write;
+
+fn fixture_path(name: &str, extension: &str) -> String {
+    format!(
+        "{}/tests/fixtures/xrandr/{}.{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name,
+        extension
+    )
+}
+
+fn fixture(name: &str) -> String {
+    std::fs::read_to_string(fixture_path(name, "txt")).unwrap()
+}
+
+/// Describes everything the parser extracts from the fixture, one line per monitor or output.
+fn describe(xrandr_output: &str) -> String {
+    let mut description = String::from("# monitors\n");
+
+    for monitor in parse_xrandr_output(xrandr_output).unwrap() {
+        let rect = monitor.rect();
+        write!(
+            description,
+            "{} {}x{}+{}+{} crtc={} rotation={}",
+            monitor.name(),
+            rect.width(),
+            rect.height(),
+            rect.offset().x(),
+            rect.offset().y(),
+            monitor.crtc(),
+            monitor.rotation().name()
+        )
+        .unwrap();
+
+        if monitor.is_primary() {
+            description.push_str(" primary");
+        }
+        if monitor.scale() != Scale::ONE {
+            write!(description, " scale={}", monitor.scale()).unwrap();
+        }
+        write!(description, " modes={}", monitor.modes().len()).unwrap();
+        if let Some(mode) = monitor.current_mode() {
+            write!(description, " current=\"{}\"", mode).unwrap();
+        }
+        if let Some(mode) = monitor.preferred_mode() {
+            write!(description, " preferred=\"{}\"", mode).unwrap();
+        }
+        if let Some(identity) = monitor.identity() {
+            write!(description, " identity=\"{}\"", identity).unwrap();
+        }
+        description.push('\n');
+    }
+
+    description.push_str("# connected outputs\n");
+
+    for output in parse_xrandr_connected_outputs(xrandr_output).unwrap() {
+        description.push_str(output.name());
+        if let Some(identity) = output.identity() {
+            write!(description, " identity=\"{}\"", identity).unwrap();
+        }
+        description.push('\n');
+    }
+
+    description
+}
+
+fn assert_golden(name: &str) {
+    let actual = describe(&fixture(name));
+    let golden_path = fixture_path(name, "golden");
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(&golden_path, &actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&golden_path).unwrap();
+    assert_eq!(actual, expected, "{} does not match {}.golden", name, name);
+}
+
+#[test]
+fn intel_mst_dock() {
+    assert_golden("intel-mst-dock");
+}
+
+#[test]
+fn intel_legacy_driver() {
+    assert_golden("intel-legacy-driver");
+}
+
+#[test]
+fn amdgpu() {
+    assert_golden("amdgpu");
+}
+
+#[test]
+fn nvidia() {
+    assert_golden("nvidia");
+}
+
+#[test]
+fn displaylink() {
+    assert_golden("displaylink");
+}
+
+#[test]
+fn rotated() {
+    assert_golden("rotated");
+}
+
+#[test]
+fn panned() {
+    assert_golden("panned");
+}
+
+#[test]
+fn disabled_connected() {
+    assert_golden("disabled-connected");
+}
+
+#[test]
+fn virtual_machine() {
+    assert_golden("virtual-machine");
+}
+
+#[test]
+fn brief() {
+    assert_golden("brief");
+}
+
+#[test]
+fn unplugged_while_enabled() {
+    assert_golden("unplugged-while-enabled");
+}
+
+#[test]
+fn scaled() {
+    assert_golden("scaled");
+}
+
+#[test]
+fn accepts_every_naming_scheme() {
+    let names = [
+        "eDP-1",
+        "eDP1",
+        "eDP",
+        "DP-1-1",
+        "HDMI-A-0",
+        "DisplayPort-0",
+        "DVI-I-1-1",
+        "Virtual1",
+        "DP-0.8",
+    ];
+
+    for name in names {
+        let line = format!(
+            "{} connected 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm",
+            name
+        );
+        let monitors = parse_xrandr_output(&line).unwrap();
+
+        assert_eq!(monitors.len(), 1, "{} was not parsed", name);
+        assert_eq!(monitors[0].name(), name);
+    }
+}
+
+#[test]
+fn panning_does_not_replace_the_geometry() {
+    let monitors = parse_xrandr_output(&fixture("panned")).unwrap();
+
+    assert_eq!(monitors[0].rect(), &Rect::new(1920, 1080, Point::new(0, 0)));
+}
+
+#[test]
+fn disabled_outputs_are_connected_but_not_monitors() {
+    let xrandr_output = fixture("disabled-connected");
+
+    let monitors = parse_xrandr_output(&xrandr_output).unwrap();
+    assert!(monitors.iter().all(|m| m.name() != "HDMI-1"));
+
+    let outputs = parse_xrandr_connected_outputs(&xrandr_output).unwrap();
+    let disabled = outputs.iter().find(|o| o.name() == "HDMI-1").unwrap();
+    assert_eq!(
+        disabled.identity().unwrap().product_name(),
+        Some("DELL U2719D")
+    );
+}
+
+#[test]
+fn rotation_is_read_with_mode_ids() {
+    let monitors = parse_xrandr_output(&fixture("rotated")).unwrap();
+    let rotations = monitors.iter().map(|m| m.rotation()).collect::<Vec<_>>();
+
+    assert_eq!(
+        rotations,
+        [Rotation::Left, Rotation::Inverted, Rotation::Right]
+    );
+}
+
+#[test]
+fn reflection_is_read_after_rotation() {
+    let xrandr_output = "DP-1 connected 1080x1920+0+0 (0x47) left X axis (normal left inverted right x axis y axis) 527mm x 296mm\n\
+        HDMI-1 connected 1920x1080+1080+0 (0x48) normal X and Y axis (normal left inverted right x axis y axis) 527mm x 296mm\n";
+    let outputs = parse_xrandr_outputs(xrandr_output).unwrap();
+
+    assert_eq!(outputs[0].rotation(), Rotation::Left);
+    assert_eq!(outputs[0].reflection(), Reflection::X);
+    assert_eq!(outputs[0].physical_size(), Some((527, 296)));
+    assert_eq!(outputs[1].rotation(), Rotation::Normal);
+    assert_eq!(outputs[1].reflection(), Reflection::XY);
+
+    let setup = MonitorSetup::new(parse_xrandr_output(xrandr_output).unwrap());
+    let args = setup.to_xrandr_args();
+    assert!(args.windows(2).any(|pair| pair == ["--reflect", "x"]));
+    assert!(args.windows(2).any(|pair| pair == ["--reflect", "xy"]));
+}
+
+#[test]
+fn empty_output_has_no_monitors() {
+    assert_eq!(parse_xrandr_output("").unwrap(), []);
+}
+
+#[test]
+fn crtc_before_any_output_is_an_error() {
+    let error = parse_xrandr_output("\tCRTC:       0\neDP-1 connected 1920x1080+0+0").unwrap_err();
+
+    assert!(matches!(
+        error,
+        XRandrParseError::OrphanedLine { line: 1, .. }
+    ));
+}
+
+#[test]
+fn crtc_of_a_disconnected_output_is_ignored() {
+    let xrandr_output = "Screen 0: minimum 320 x 200, current 1920 x 1080, maximum 16384 x 16384\n\
+        \tCRTC:       3\n\
+        DP-1 disconnected 1920x1080+0+0 (0x47) normal (normal left inverted right x axis y axis) 0mm x 0mm\n\
+        \tCRTC:       0\n";
+
+    assert_eq!(parse_xrandr_output(xrandr_output).unwrap(), []);
+}
+
+#[test]
+fn invalid_lines_are_errors() {
+    let invalid_crtc = "eDP-1 connected 1920x1080+0+0 (0x47) normal\n\tCRTC:       first";
+    assert_eq!(
+        parse_xrandr_output(invalid_crtc).unwrap_err(),
+        XRandrParseError::InvalidCrtc {
+            line: 2,
+            output: "eDP-1".to_owned(),
+            crtc: "first".to_owned(),
+        }
+    );
+
+    let invalid_geometry = "eDP-1 connected 1920x+0+0 (normal left inverted right x axis y axis)";
+    let error = parse_xrandr_output(invalid_geometry).unwrap_err();
+    assert!(matches!(
+        error,
+        XRandrParseError::InvalidGeometry { line: 1, .. }
+    ));
+
+    let unexpected = "Screen 0: minimum 320 x 200\nCan't open display";
+    let error = parse_xrandr_output(unexpected).unwrap_err();
+    assert_eq!(error.line(), 2);
+    assert!(matches!(error, XRandrParseError::UnexpectedLine { .. }));
+}
+
+#[test]
+fn verbose_modes_are_read_from_their_timings() {
+    let xrandr_output = "HDMI-1 connected 1920x1080+0+0 (0x50) normal (normal left inverted right x axis y axis) 0mm x 0mm\n\
+        \x20 1920x1080i (0x50) 74.250MHz -HSync -VSync Interlace *current\n\
+        \x20       h: width  1920 start 2008 end 2052 total 2200 skew    0 clock  33.75KHz\n\
+        \x20       v: height 1080 start 1084 end 1094 total 1125           clock  60.00Hz\n";
+    let monitors = parse_xrandr_output(xrandr_output).unwrap();
+
+    let mode = monitors[0].current_mode().unwrap();
+    assert_eq!(
+        *mode,
+        Mode::new(1920, 1080, 60000)
+            .with_current(true)
+            .with_interlaced(true)
+    );
+    assert_eq!(monitors[0].preferred_mode(), None);
+}
+
+#[test]
+fn running_below_native_resolution_is_a_warning() {
+    let monitors = parse_xrandr_output(&fixture("brief")).unwrap();
+    let report = MonitorSetup::new(monitors).validate();
+
+    let issue = report
+        .issues()
+        .iter()
+        .find(|issue| matches!(issue, LayoutIssue::BelowNativeResolution { .. }))
+        .unwrap();
+    assert!(!issue.is_error());
+    assert_eq!(
+        issue.to_string(),
+        "HDMI-1 runs at 1280x1024, below its native resolution of 1920x1080"
+    );
+}
+
+#[test]
+fn inventory_includes_disabled_and_disconnected_outputs() {
+    let outputs = parse_xrandr_outputs(&fixture("amdgpu")).unwrap();
+    let inventory = OutputInventory::new(outputs);
+
+    let disconnected = inventory.output_by_name("HDMI-A-0").unwrap();
+    assert_eq!(disconnected.connection(), ConnectionState::Disconnected);
+    assert!(!disconnected.is_enabled());
+    assert_eq!(disconnected.possible_crtcs(), [0, 1, 2, 3]);
+
+    let rotated = inventory.output_by_name("DisplayPort-0").unwrap();
+    assert_eq!(rotated.crtc(), Some(1));
+    assert_eq!(rotated.physical_size(), Some((597, 336)));
+    assert_eq!(
+        rotated.rotations(),
+        [
+            Rotation::Normal,
+            Rotation::Left,
+            Rotation::Inverted,
+            Rotation::Right
+        ]
+    );
+    assert_eq!(
+        rotated.preferred_mode(),
+        Some(
+            &Mode::new(2560, 1440, 59950)
+                .with_current(true)
+                .with_preferred(true)
+        )
+    );
+
+    let outputs = parse_xrandr_outputs(&fixture("disabled-connected")).unwrap();
+    let inventory = OutputInventory::new(outputs);
+    let disabled = inventory.disabled().map(|o| o.name()).collect::<Vec<_>>();
+    assert_eq!(disabled, ["HDMI-1"]);
+    assert_eq!(
+        inventory.output_by_name("VGA-1").unwrap().connection(),
+        ConnectionState::Unknown
+    );
+}
+
+#[test]
+fn scaled_outputs_reproduce_their_scale() {
+    let setup = MonitorSetup::new(parse_xrandr_output(&fixture("scaled")).unwrap());
+    let panel = setup.monitor_by_name("eDP-1").unwrap();
+
+    assert_eq!(panel.scale(), Scale::from_120ths(240).unwrap());
+    assert_eq!(
+        setup.physical_rect(panel),
+        Rect::new(3840, 2160, Point::new(0, 0))
+    );
+
+    let args = setup.to_xrandr_args();
+    let panel_args = args
+        .split(|arg| arg == "--output")
+        .find(|args| args.first().map(String::as_str) == Some("eDP-1"))
+        .unwrap();
+    assert!(panel_args
+        .windows(2)
+        .any(|pair| pair == ["--mode", "3840x2160"]));
+    assert!(panel_args
+        .windows(2)
+        .any(|pair| pair == ["--scale", "0.5x0.5"]));
+}